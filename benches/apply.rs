@@ -0,0 +1,27 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rustytwisty::cube::{
+    cube::Cube, facelets::Facelets, moves::Move, scramble::scramble_iter,
+};
+
+fn apply(c: &mut Criterion) {
+    let moves: Vec<Move> = scramble_iter(648).take(1000).collect();
+
+    c.bench_function("Cube::apply", |b| {
+        let mut cube = Cube::solved();
+
+        b.iter(|| {
+            cube.apply_sequence(black_box(&moves));
+        })
+    });
+
+    c.bench_function("Facelets::apply", |b| {
+        let mut facelets = Facelets::solved();
+
+        b.iter(|| {
+            facelets.apply_sequence(black_box(&moves));
+        })
+    });
+}
+
+criterion_group!(benches, apply);
+criterion_main!(benches);
@@ -0,0 +1,152 @@
+//! An interactive cube which reads moves from standard input and prints the
+//! cube after each line.
+//!
+//! Besides moves in standard notation, the commands `scramble [length]`,
+//! `solve`, `undo`, `reset` and `help` are understood. The REPL exits at the
+//! end of its input.
+//!
+//! With the `kociemba` feature, `solve` uses the two phase solver and can
+//! solve any scramble. Without it, `solve` searches for an optimal solution,
+//! which is only practical a few moves from solved, so scrambles default to
+//! that length instead.
+//!
+//! ```text
+//! cargo run --example repl --features kociemba
+//! ```
+
+use rustytwisty::cube::{
+    cube::Cube,
+    cubie::PaletteScheme,
+    moves::{format_sequence, Move},
+    solver::Solver,
+};
+use std::io::{self, BufRead, Write};
+
+/// The longest solution `solve` searches for before giving up.
+#[cfg(feature = "kociemba")]
+const MAX_SOLVE_DEPTH: usize = 24;
+
+/// The longest solution `solve` searches for before giving up, since the
+/// optimal solver slows down exponentially with depth.
+#[cfg(not(feature = "kociemba"))]
+const MAX_SOLVE_DEPTH: usize = 5;
+
+/// The length of a scramble when none is given.
+#[cfg(feature = "kociemba")]
+const SCRAMBLE_LENGTH: usize = 20;
+
+/// The length of a scramble when none is given, short enough for `solve` to
+/// undo.
+#[cfg(not(feature = "kociemba"))]
+const SCRAMBLE_LENGTH: usize = MAX_SOLVE_DEPTH;
+
+/// Returns the solver used by `solve`.
+#[cfg(feature = "kociemba")]
+fn solver() -> impl Solver {
+    rustytwisty::cube::kociemba::TwoPhaseSolver::new(MAX_SOLVE_DEPTH)
+}
+
+/// Returns the solver used by `solve`.
+#[cfg(not(feature = "kociemba"))]
+fn solver() -> impl Solver {
+    rustytwisty::cube::solver::OptimalSolver::new(MAX_SOLVE_DEPTH)
+}
+
+fn help() -> String {
+    format!(
+        "\
+Moves:              R U R' U' (any standard notation)
+scramble [length]   apply a random scramble, {} moves by default
+solve               find and apply a solution of up to {} moves
+undo                undo the last line of moves
+reset               return to a solved cube
+help                show this message",
+        SCRAMBLE_LENGTH, MAX_SOLVE_DEPTH
+    )
+}
+
+fn main() -> io::Result<()> {
+    let mut cube = Cube::solved();
+    let mut history: Vec<Vec<Move>> = Vec::new();
+    let mut rng = rand::thread_rng();
+    let solver = solver();
+
+    print_cube(&cube);
+    prompt()?;
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+
+        match words.next() {
+            None => {}
+            Some("help") => println!("{}", help()),
+            Some("reset") => {
+                cube = Cube::solved();
+                history.clear();
+                print_cube(&cube);
+            }
+            Some("undo") => match history.pop() {
+                Some(moves) => {
+                    for m in moves.iter().rev() {
+                        cube.apply(m.inverse());
+                    }
+                    print_cube(&cube);
+                }
+                None => println!("Nothing to undo"),
+            },
+            Some("scramble") => {
+                match words.next().map_or(Ok(SCRAMBLE_LENGTH), str::parse) {
+                    Ok(len) => {
+                        let scramble = cube.scramble(len, &mut rng);
+
+                        println!("{}", format_sequence(&scramble, 0));
+                        history.push(scramble);
+                        print_cube(&cube);
+                    }
+                    Err(e) => println!("Invalid length: {}", e),
+                }
+            }
+            Some("solve") => match solver.solve(&cube) {
+                Some(solution) => {
+                    cube.apply_sequence(&solution);
+
+                    println!("{}", format_sequence(&solution, 0));
+                    history.push(solution);
+                    print_cube(&cube);
+                }
+                None => {
+                    println!("No solution within {} moves", MAX_SOLVE_DEPTH)
+                }
+            },
+            Some(_) => match Move::parse_sequence(&line) {
+                Ok(moves) => {
+                    cube.apply_sequence(&moves);
+                    history.push(moves);
+                    print_cube(&cube);
+                }
+                Err(e) => println!("Couldn't parse moves: {}", e),
+            },
+        }
+
+        prompt()?;
+    }
+
+    println!();
+
+    Ok(())
+}
+
+/// Prints the cube's net in color, and whether it is solved.
+fn print_cube(cube: &Cube) {
+    print!("{}", cube.to_ansi_string(PaletteScheme::Standard));
+
+    if cube.is_solved() {
+        println!("Solved!");
+    }
+}
+
+fn prompt() -> io::Result<()> {
+    print!("> ");
+    io::stdout().flush()
+}
@@ -0,0 +1,119 @@
+//! Solves every scramble in a file, one scramble per line, printing each
+//! solution and its length followed by the total time taken.
+//!
+//! Blank lines and `//` comments are skipped, and lines which can't be parsed
+//! are reported by line number.
+//!
+//! With the `kociemba` feature, scrambles are solved by the two phase solver,
+//! whose tables are built once before the first line. Without it, scrambles
+//! are solved optimally, so only short scrambles finish within the time
+//! allowed for each.
+//!
+//! ```text
+//! cargo run --release --features kociemba \
+//!     --example solve_file -- scrambles.txt
+//! ```
+
+use rustytwisty::cube::{
+    cube::Cube,
+    moves::{format_sequence, Move},
+};
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Instant;
+
+/// The longest solution to search for before moving on to the next scramble.
+#[cfg(feature = "kociemba")]
+const MAX_MOVES: usize = 24;
+
+/// How long to search for each solution before moving on to the next.
+#[cfg(not(feature = "kociemba"))]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Returns a function which solves a cube, building the two phase solver's
+/// tables up front so they're shared by every line.
+#[cfg(feature = "kociemba")]
+fn solver() -> impl Fn(&Cube) -> Option<Vec<Move>> {
+    use rustytwisty::cube::{kociemba::TwoPhaseSolver, solver::Solver};
+
+    let solver = TwoPhaseSolver::new(MAX_MOVES);
+    move |cube: &Cube| solver.solve(cube)
+}
+
+/// Returns a function which solves a cube optimally, giving up after
+/// `TIMEOUT`.
+#[cfg(not(feature = "kociemba"))]
+fn solver() -> impl Fn(&Cube) -> Option<Vec<Move>> {
+    |cube: &Cube| cube.solve_within(TIMEOUT)
+}
+
+/// Describes how far `solver` searches, for lines it fails to solve.
+#[cfg(feature = "kociemba")]
+fn limit() -> String {
+    format!("{} moves", MAX_MOVES)
+}
+
+/// Describes how far `solver` searches, for lines it fails to solve.
+#[cfg(not(feature = "kociemba"))]
+fn limit() -> String {
+    format!("{:?}", TIMEOUT)
+}
+
+fn main() {
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: solve_file <path>");
+            process::exit(2);
+        }
+    };
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Couldn't read {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let start = Instant::now();
+    let solve = solver();
+    let (mut solved, mut failed) = (0, 0);
+
+    for (line, scramble) in Move::parse_lines(&input) {
+        let scramble = match scramble {
+            Ok(scramble) => scramble,
+            Err(e) => {
+                eprintln!("Line {}: {}", line, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&scramble);
+
+        match solve(&cube) {
+            Some(solution) => {
+                println!(
+                    "Line {}: {} ({} moves)",
+                    line,
+                    format_sequence(&solution, 0),
+                    solution.len()
+                );
+                solved += 1;
+            }
+            None => {
+                println!("Line {}: no solution within {}", line, limit());
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Solved {} of {} scrambles in {:.2?}",
+        solved,
+        solved + failed,
+        start.elapsed()
+    );
+}
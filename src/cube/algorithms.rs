@@ -0,0 +1,69 @@
+use crate::cube::moves::Move;
+
+/// Well known algorithms, as pairs of a name and the algorithm in standard
+/// notation.
+const ALGORITHMS: [(&str, &str); 9] = [
+    ("sexy-move", "R U R' U'"),
+    ("sledgehammer", "R' F R F'"),
+    ("sune", "R U R' U R U2 R'"),
+    ("anti-sune", "R U2 R' U' R U' R'"),
+    ("t-perm", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("jb-perm", "R U R' F' R U R' U' R' F R2 U' R' U'"),
+    ("y-perm", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("ua-perm", "M2 U M U2 M' U M2"),
+    ("ub-perm", "M2 U' M U2 M' U' M2"),
+];
+
+/// Returns a well known algorithm by its name, or None if there's no
+/// algorithm with that name.
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::{algorithms::*, moves::*};
+/// let sexy = alg("sexy-move").unwrap();
+///
+/// assert_eq!(sexy, vec![Move::R, Move::U, Move::R_PRIME, Move::U_PRIME]);
+/// ```
+pub fn alg(name: &str) -> Option<Vec<Move>> {
+    ALGORITHMS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .and_then(|(_, notation)| Move::parse_sequence(notation).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::cube::Cube;
+
+    #[test]
+    fn lookup() {
+        assert_eq!(
+            alg("sexy-move"),
+            Some(Move::parse_sequence("R U R' U'").unwrap())
+        );
+        assert_eq!(alg("not-an-alg"), None);
+
+        for (name, _) in ALGORITHMS.iter() {
+            assert!(alg(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn permutations_have_expected_order() {
+        for (name, order) in
+            &[("t-perm", 2), ("jb-perm", 2), ("y-perm", 2), ("ua-perm", 3)]
+        {
+            let moves = alg(name).unwrap();
+            let mut cube = Cube::solved();
+
+            for i in 1..=*order {
+                cube.apply_sequence(&moves);
+
+                assert_eq!(cube.f2l_pairs_solved(), 4, "{}", name);
+                assert_eq!(cube.is_solved(), i == *order, "{}", name);
+            }
+        }
+    }
+}
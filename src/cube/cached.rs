@@ -0,0 +1,164 @@
+use crate::cube::cube::{
+    ansi_net, face_facelet_indices, format_net, Cube, FaceKind,
+};
+use crate::cube::cubie::{Color, PaletteScheme};
+use crate::cube::facelets::Facelets;
+use crate::cube::moves::Move;
+use std::fmt;
+
+/// A [`Cube`] which keeps a copy of its stickers' colors as [`Facelets`],
+/// turned along with the cube on every move.
+///
+/// Reading colors from a [`Cube`] means going through each of its cubies,
+/// while the copy is a plain array, so checking whether the cube is solved or
+/// reading its colors to draw it is much faster.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cached::*, cube::*, moves::*};
+/// let mut cube = CachedCube::solved();
+/// cube.apply(Move::R);
+///
+/// assert!(!cube.is_solved());
+/// assert_eq!(cube.facelets(), &cube.cube().facelets());
+/// ```
+#[derive(Clone)]
+pub struct CachedCube {
+    cube: Cube,
+    facelets: Facelets,
+}
+
+impl CachedCube {
+    /// Returns a solved cube with the standard Western color scheme.
+    pub fn solved() -> Self {
+        Self::from(Cube::solved())
+    }
+
+    /// Returns the underlying cube.
+    pub const fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Returns the cached colors of all 54 stickers of the cube, in the same
+    /// order as [`Cube::facelets`].
+    pub const fn facelets(&self) -> &[Color; 54] {
+        &self.facelets.0
+    }
+
+    /// Returns the cached colors of the nine stickers on a face of the cube,
+    /// in the same order as [`Cube::face_colors`].
+    pub fn face_colors(&self, face: FaceKind) -> [Color; 9] {
+        let mut colors = [Color::Uninit; 9];
+
+        for (color, i) in colors.iter_mut().zip(&face_facelet_indices(face)) {
+            *color = self.facelets.0[*i];
+        }
+
+        colors
+    }
+
+    /// Returns the net of the cube drawn from its cached colors, the same as
+    /// [`Cube::to_ansi_string`].
+    pub fn to_ansi_string(&self, scheme: PaletteScheme) -> String {
+        ansi_net(self.facelets(), scheme)
+    }
+
+    /// Returns true if every face of the cube is a single color.
+    pub fn is_solved(&self) -> bool {
+        self.facelets.is_solved()
+    }
+
+    /// Applies a [`Move`] to both the cube and its cached colors.
+    pub fn apply(&mut self, m: Move) -> &mut Self {
+        self.cube.apply(m);
+        self.facelets.apply(m);
+
+        self
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order.
+    pub fn apply_sequence(&mut self, moves: &[Move]) -> &mut Self {
+        for m in moves {
+            self.apply(*m);
+        }
+
+        self
+    }
+}
+
+/// Formats the net of the cube from its cached colors, the same way as the
+/// [`Display`](fmt::Display) implementation of [`Cube`].
+impl fmt::Display for CachedCube {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        format_net(self.facelets(), f)
+    }
+}
+
+impl From<Cube> for CachedCube {
+    fn from(cube: Cube) -> Self {
+        let facelets = Facelets::from(&cube);
+
+        Self {
+            cube,
+            facelets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::moves::{Direction, Layer};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn cache_matches_cube() {
+        let mut rng = StdRng::seed_from_u64(677);
+        let layers = [
+            Layer::U,
+            Layer::D,
+            Layer::L,
+            Layer::R,
+            Layer::F,
+            Layer::B,
+            Layer::Rw,
+            Layer::Dw,
+            Layer::M,
+            Layer::E,
+            Layer::S,
+            Layer::X,
+            Layer::Y,
+            Layer::Z,
+        ];
+        let mut cube = CachedCube::solved();
+
+        for _ in 0..300 {
+            let layer = layers[rng.gen_range(0, layers.len())];
+            let direction = Direction::from_quarter_turns(rng.gen_range(1, 4));
+            cube.apply(Move::new(layer, direction.unwrap()));
+
+            assert_eq!(cube.facelets(), &cube.cube().facelets());
+            assert_eq!(cube.is_solved(), cube.cube().is_solved());
+
+            for face in FaceKind::all().iter() {
+                assert_eq!(
+                    cube.face_colors(*face),
+                    cube.cube().face_colors(*face)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn renders_like_cube() {
+        let mut cube = CachedCube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F' M2 y").unwrap());
+
+        assert_eq!(cube.to_string(), cube.cube().to_string());
+        assert_eq!(format!("{:>30}", cube), format!("{:>30}", cube.cube()));
+        assert_eq!(
+            cube.to_ansi_string(PaletteScheme::Standard),
+            cube.cube().to_ansi_string(PaletteScheme::Standard)
+        );
+    }
+}
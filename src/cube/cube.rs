@@ -1,4 +1,16 @@
-use crate::cube::cubie::Cubie;
+use crate::cube::cubie::{Color, Cubie, PaletteScheme};
+use crate::cube::moves::{
+    optimize, Direction, Layer, Move, DIRECTIONS, FACE_LAYERS,
+};
+use crate::cube::scramble::{MoveWeights, ScrambleIter};
+use crate::cube::solver::{legal_moves_after, OptimalSolver, Solver};
+use rand::Rng;
+use staticvec::StaticVec;
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
 
 pub struct Face<'a> {
     pub elements: [&'a Box<dyn Cubie>; 9],
@@ -12,6 +24,7 @@ impl<'a> Face<'a> {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FaceKind {
     Top,
     Left,
@@ -21,6 +34,550 @@ pub enum FaceKind {
     Bottom,
 }
 
+impl FaceKind {
+    /// Returns every face of the cube, each exactly once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::solved();
+    ///
+    /// for face in FaceKind::all().iter() {
+    ///     let colors = cube.face_colors(*face);
+    ///
+    ///     assert!(colors.iter().all(|c| *c == colors[4]));
+    /// }
+    /// ```
+    pub const fn all() -> [FaceKind; 6] {
+        [
+            FaceKind::Top,
+            FaceKind::Left,
+            FaceKind::Right,
+            FaceKind::Front,
+            FaceKind::Back,
+            FaceKind::Bottom,
+        ]
+    }
+
+    /// Returns the [`Layer`] which turns this face.
+    pub const fn layer(&self) -> Layer {
+        match self {
+            FaceKind::Top => Layer::U,
+            FaceKind::Left => Layer::L,
+            FaceKind::Right => Layer::R,
+            FaceKind::Front => Layer::F,
+            FaceKind::Back => Layer::B,
+            FaceKind::Bottom => Layer::D,
+        }
+    }
+
+    /// Returns the unit vector pointing out of the face, where x runs left to
+    /// right, y runs bottom to top, and z runs back to front.
+    pub(crate) const fn normal(&self) -> (i8, i8, i8) {
+        match self {
+            FaceKind::Top => (0, 1, 0),
+            FaceKind::Left => (-1, 0, 0),
+            FaceKind::Right => (1, 0, 0),
+            FaceKind::Front => (0, 0, 1),
+            FaceKind::Back => (0, 0, -1),
+            FaceKind::Bottom => (0, -1, 0),
+        }
+    }
+
+    pub(crate) const fn from_normal(normal: (i8, i8, i8)) -> Self {
+        match normal {
+            (0, 1, 0) => FaceKind::Top,
+            (-1, 0, 0) => FaceKind::Left,
+            (1, 0, 0) => FaceKind::Right,
+            (0, 0, 1) => FaceKind::Front,
+            (0, 0, -1) => FaceKind::Back,
+            (0, -1, 0) => FaceKind::Bottom,
+            _ => panic!("Not a face normal"),
+        }
+    }
+}
+
+/// Faces of the cube in the order used for facelet strings: up, right, front,
+/// down, left, back.
+const FACELET_FACES: [FaceKind; 6] = [
+    FaceKind::Top,
+    FaceKind::Right,
+    FaceKind::Front,
+    FaceKind::Bottom,
+    FaceKind::Left,
+    FaceKind::Back,
+];
+
+/// The row and column of the top left sticker of each face on the flattened
+/// net, with faces in facelet order: up, right, front, down, left, back.
+const NET_ORIGINS: [(u32, u32); 6] =
+    [(0, 3), (3, 6), (3, 3), (6, 3), (3, 0), (3, 9)];
+
+/// Returns the position of a facelet given its face and its row and column
+/// within the face, each from 0 to 2. Faces are read left to right and top to
+/// bottom as seen on a flattened net, the top face with the back at the top,
+/// the bottom face with the front at the top, and the rest with the top face
+/// above them.
+const fn facelet_position(face: FaceKind, row: i8, col: i8) -> (i8, i8, i8) {
+    // The directions a face's columns and rows run in on the net
+    let (right, down) = match face {
+        FaceKind::Top => ((1, 0, 0), (0, 0, 1)),
+        FaceKind::Right => ((0, 0, -1), (0, -1, 0)),
+        FaceKind::Front => ((1, 0, 0), (0, -1, 0)),
+        FaceKind::Bottom => ((1, 0, 0), (0, 0, -1)),
+        FaceKind::Left => ((0, 0, 1), (0, -1, 0)),
+        FaceKind::Back => ((-1, 0, 0), (0, -1, 0)),
+    };
+    let (r, c) = (row - 1, col - 1);
+    let n = face.normal();
+
+    (
+        n.0 + r * down.0 + c * right.0,
+        n.1 + r * down.1 + c * right.1,
+        n.2 + r * down.2 + c * right.2,
+    )
+}
+
+/// Returns the index in [`Cube::facelets`] of each sticker on a face, in the
+/// same order as [`Cube::face_colors`].
+pub(crate) fn face_facelet_indices(face: FaceKind) -> [usize; 9] {
+    let f = match FACELET_FACES.iter().position(|f| *f == face) {
+        Some(f) => f,
+        None => panic!("Not a facelet face"),
+    };
+    let order = face_sticker_order(face);
+    let mut indices = [0; 9];
+
+    for (index, element) in indices.iter_mut().zip(&face_elements(face)) {
+        if let Some(k) = order.iter().position(|e| e == element) {
+            *index = f * 9 + k;
+        }
+    }
+
+    indices
+}
+
+/// Returns the index of each element on a face, in the order they're held in
+/// [`Cube::elements`], as used by [`Cube::face`].
+pub(crate) const fn face_elements(face: FaceKind) -> [usize; 9] {
+    let order = face_sticker_order(face);
+    let mut elements = [0; 9];
+    let mut n = 0;
+    let mut i = 0;
+
+    while i < POSITIONS.len() {
+        let mut k = 0;
+
+        while k < order.len() {
+            if order[k] == i {
+                elements[n] = i;
+                n += 1;
+            }
+            k += 1;
+        }
+        i += 1;
+    }
+
+    elements
+}
+
+/// Returns the index of the element holding each sticker of a face, in the
+/// order the stickers are read in [`Cube::facelets`]: left to right and top
+/// to bottom as seen on a flattened net.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::cube::*;
+/// assert_eq!(face_sticker_order(FaceKind::Top), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+/// assert_eq!(face_sticker_order(FaceKind::Front)[0], 6);
+/// ```
+pub const fn face_sticker_order(face: FaceKind) -> [usize; 9] {
+    let mut order = [0; 9];
+    let mut n = 0;
+
+    while n < 9 {
+        let pos = facelet_position(face, n as i8 / 3, n as i8 % 3);
+        order[n] = element_index(pos);
+        n += 1;
+    }
+
+    order
+}
+
+/// Returns the color of a face of a solved cube using the standard Western
+/// color scheme, held with white on top and green in front.
+const fn solved_color(face: FaceKind) -> Color {
+    match face {
+        FaceKind::Top => Color::White,
+        FaceKind::Left => Color::Orange,
+        FaceKind::Right => Color::Red,
+        FaceKind::Front => Color::Green,
+        FaceKind::Back => Color::Blue,
+        FaceKind::Bottom => Color::Yellow,
+    }
+}
+
+/// The [key](Cube::key) of a solved cube held in the standard orientation,
+/// with white on top and green in front.
+pub const SOLVED_KEY: [u8; 27] = solved_key();
+
+/// The index in [`Cube::facelets`] of each sticker of each element, in the
+/// order the element's cubie stores its faces. Entries past an element's last
+/// face are unused.
+const ELEMENT_FACELETS: [[usize; 3]; 26] = element_facelets();
+
+const fn element_facelets() -> [[usize; 3]; 26] {
+    let mut facelets = [[0; 3]; 26];
+    let mut f = 0;
+
+    while f < FACELET_FACES.len() {
+        let face = FACELET_FACES[f];
+        let order = face_sticker_order(face);
+        let normal = face.normal();
+        let mut n = 0;
+
+        while n < order.len() {
+            let i = order[n];
+            let (_, y, z) = POSITIONS[i];
+
+            // Cubies store their top or bottom face first, then their front
+            // or back face, then their left or right face
+            let k = if normal.1 != 0 {
+                0
+            } else if normal.2 != 0 {
+                (y != 0) as usize
+            } else {
+                (y != 0) as usize + (z != 0) as usize
+            };
+
+            facelets[i][k] = f * 9 + n;
+            n += 1;
+        }
+        f += 1;
+    }
+
+    facelets
+}
+
+/// Returns how far the color of a facelet is shifted within its byte of a
+/// [key](Cube::key): the first facelet of each pair is in the high four bits.
+const fn key_shift(facelet: usize) -> u8 {
+    if facelet % 2 == 0 {
+        4
+    } else {
+        0
+    }
+}
+
+const fn solved_key() -> [u8; 27] {
+    let mut key = [0; 27];
+    let mut i = 0;
+
+    while i < 54 {
+        let color = solved_color(FACELET_FACES[i / 9]) as u8;
+        key[i / 2] |= color << key_shift(i);
+        i += 1;
+    }
+
+    key
+}
+
+/// The (x, y, z) coordinates of every element of [`Cube`], indexed the same
+/// way as [`Cube::elements`]. x runs left to right, y runs bottom to top, and z
+/// runs back to front, so (0, 0, 0) is the core of the cube, which has no
+/// element.
+pub const POSITIONS: [(i8, i8, i8); 26] = [
+    (-1, 1, -1), // Top slice
+    (0, 1, -1),
+    (1, 1, -1),
+    (-1, 1, 0),
+    (0, 1, 0),
+    (1, 1, 0),
+    (-1, 1, 1),
+    (0, 1, 1),
+    (1, 1, 1),
+    (-1, 0, -1), // Middle slice
+    (0, 0, -1),
+    (1, 0, -1),
+    (-1, 0, 0),
+    (1, 0, 0),
+    (-1, 0, 1),
+    (0, 0, 1),
+    (1, 0, 1),
+    (-1, -1, -1), // Bottom slice
+    (0, -1, -1),
+    (1, -1, -1),
+    (-1, -1, 0),
+    (0, -1, 0),
+    (1, -1, 0),
+    (-1, -1, 1),
+    (0, -1, 1),
+    (1, -1, 1),
+];
+
+/// Returns the (x, y, z) coordinates of an element of [`Cube`].
+pub(crate) const fn element_position(index: usize) -> (i8, i8, i8) {
+    POSITIONS[index]
+}
+
+/// Returns the index of the element of [`Cube`] at a set of coordinates.
+pub(crate) const fn element_index(pos: (i8, i8, i8)) -> usize {
+    let mut i = 0;
+
+    while i < POSITIONS.len() {
+        let p = POSITIONS[i];
+        if p.0 == pos.0 && p.1 == pos.1 && p.2 == pos.2 {
+            return i;
+        }
+        i += 1;
+    }
+
+    panic!("No element at position")
+}
+
+/// Returns the faces of the cube that an element's cubie faces point towards,
+/// in the order the cubie stores them: top or bottom first, then front or back,
+/// then left or right.
+pub(crate) fn element_faces(index: usize) -> StaticVec<FaceKind, 3> {
+    let (x, y, z) = element_position(index);
+    let mut faces = StaticVec::new();
+
+    if y != 0 {
+        faces.push(FaceKind::from_normal((0, y, 0)));
+    }
+    if z != 0 {
+        faces.push(FaceKind::from_normal((0, 0, z)));
+    }
+    if x != 0 {
+        faces.push(FaceKind::from_normal((x, 0, 0)));
+    }
+
+    faces
+}
+
+/// Returns the coordinates of a corner, numbered in the order of
+/// [`CornerPosition`].
+const fn corner_coordinates(pos: usize) -> (i8, i8, i8) {
+    if pos >= 8 {
+        panic!("Exceeded corner count");
+    }
+
+    let x = if pos % 2 == 0 { -1 } else { 1 };
+    let y = if pos < 4 { 1 } else { -1 };
+    let z = if pos % 4 < 2 { -1 } else { 1 };
+
+    (x, y, z)
+}
+
+/// Returns the coordinates of an edge, numbered in the order of
+/// [`EdgePosition`].
+const fn edge_coordinates(pos: usize) -> (i8, i8, i8) {
+    match pos {
+        0 => (0, 1, -1),
+        1 => (-1, 1, 0),
+        2 => (1, 1, 0),
+        3 => (0, 1, 1),
+        4 => (-1, 0, -1),
+        5 => (1, 0, -1),
+        6 => (-1, 0, 1),
+        7 => (1, 0, 1),
+        8 => (0, -1, -1),
+        9 => (-1, -1, 0),
+        10 => (1, -1, 0),
+        11 => (0, -1, 1),
+        _ => panic!("Exceeded edge count"),
+    }
+}
+
+/// Returns true if a set of coordinates lies within a [`Layer`].
+pub(crate) const fn layer_contains(layer: Layer, pos: (i8, i8, i8)) -> bool {
+    let (x, y, z) = pos;
+
+    match layer {
+        Layer::U => y == 1,
+        Layer::D => y == -1,
+        Layer::L => x == -1,
+        Layer::R => x == 1,
+        Layer::F => z == 1,
+        Layer::B => z == -1,
+        Layer::M => x == 0,
+        Layer::E => y == 0,
+        Layer::S => z == 0,
+        Layer::Uw => y >= 0,
+        Layer::Dw => y <= 0,
+        Layer::Lw => x <= 0,
+        Layer::Rw => x >= 0,
+        Layer::Fw => z >= 0,
+        Layer::Bw => z <= 0,
+        Layer::X | Layer::Y | Layer::Z => true,
+    }
+}
+
+/// Rotates a set of coordinates by one clockwise quarter turn of a [`Layer`].
+pub(crate) const fn rotate_position(
+    layer: Layer,
+    pos: (i8, i8, i8),
+) -> (i8, i8, i8) {
+    let (x, y, z) = pos;
+
+    match layer {
+        Layer::R | Layer::Rw | Layer::X => (x, z, -y),
+        Layer::L | Layer::Lw | Layer::M => (x, -z, y),
+        Layer::U | Layer::Uw | Layer::Y => (-z, y, x),
+        Layer::D | Layer::Dw | Layer::E => (z, y, -x),
+        Layer::F | Layer::Fw | Layer::S | Layer::Z => (y, -x, z),
+        Layer::B | Layer::Bw => (-y, x, z),
+    }
+}
+
+/// Returns where a clockwise quarter turn of a face moves each element of
+/// [`Cube`]. The element at index `i` of [`Cube::elements`] moves to index
+/// `permutation[i]`, and elements outside of the face stay where they are.
+///
+/// Only the positions of elements are given, not how their stickers are
+/// reoriented as they move.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::cube::*;
+/// let permutation = face_permutation(FaceKind::Top);
+///
+/// // The back left corner moves to the back right
+/// assert_eq!(permutation[0], 2);
+/// // The front left corner isn't in the top face
+/// assert_eq!(permutation[23], 23);
+/// ```
+pub const fn face_permutation(face: FaceKind) -> [usize; 26] {
+    layer_permutation(face.layer())
+}
+
+/// Returns where a clockwise quarter turn of a [`Layer`] moves each element,
+/// as in [`face_permutation`].
+pub(crate) const fn layer_permutation(layer: Layer) -> [usize; 26] {
+    let mut permutation = [0; 26];
+    let mut i = 0;
+
+    while i < POSITIONS.len() {
+        let pos = POSITIONS[i];
+
+        permutation[i] = if layer_contains(layer, pos) {
+            element_index(rotate_position(layer, pos))
+        } else {
+            i
+        };
+        i += 1;
+    }
+
+    permutation
+}
+
+/// The facelet permutations of the 18 basic moves, in the order of
+/// [`Move::all_basic`]. After a move, the facelet at each index of
+/// [`Cube::facelets`] holds the color from the index given in the move's row
+/// of the table.
+///
+/// The table is worked out when compiling from the same geometry [`Cube`]
+/// turns with, and [`Facelets`](crate::cube::facelets::Facelets) turns with
+/// the table, so the two can't disagree.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*};
+/// let mut cube = Cube::solved();
+/// let before = cube.facelets();
+/// cube.apply(Move::F2);
+///
+/// let permutation = MOVE_PERMUTATIONS[14];
+/// for (i, color) in cube.facelets().iter().enumerate() {
+///     assert_eq!(*color, before[permutation[i]]);
+/// }
+/// ```
+pub const MOVE_PERMUTATIONS: [[usize; 54]; 18] = move_permutations();
+
+const fn move_permutations() -> [[usize; 54]; 18] {
+    let mut table = [[0; 54]; 18];
+    let mut f = 0;
+
+    while f < FACE_LAYERS.len() {
+        let quarter = facelet_permutation(FACE_LAYERS[f]);
+        let mut d = 0;
+
+        while d < DIRECTIONS.len() {
+            let mut permutation = [0; 54];
+            let mut i = 0;
+            while i < 54 {
+                permutation[i] = i;
+                i += 1;
+            }
+
+            let mut turns = 0;
+            while turns < DIRECTIONS[d].quarter_turns() {
+                let mut next = [0; 54];
+                let mut i = 0;
+                while i < 54 {
+                    next[i] = permutation[quarter[i]];
+                    i += 1;
+                }
+
+                permutation = next;
+                turns += 1;
+            }
+
+            table[f * 3 + d] = permutation;
+            d += 1;
+        }
+        f += 1;
+    }
+
+    table
+}
+
+/// Returns the facelet permutation for a clockwise quarter turn of a single
+/// [`Layer`], in the same form as [`MOVE_PERMUTATIONS`].
+pub(crate) const fn facelet_permutation(layer: Layer) -> [usize; 54] {
+    let mut permutation = [0; 54];
+    let mut i = 0;
+
+    while i < 54 {
+        let face = FACELET_FACES[i / 9];
+        let n = (i % 9) as i8;
+        let pos = facelet_position(face, n / 3, n % 3);
+
+        let j = if layer_contains(layer, pos) {
+            facelet_index(
+                rotate_position(layer, pos),
+                rotate_position(layer, face.normal()),
+            )
+        } else {
+            i
+        };
+        permutation[j] = i;
+        i += 1;
+    }
+
+    permutation
+}
+
+/// Returns the index in [`Cube::facelets`] of the sticker at a position which
+/// faces along a normal.
+const fn facelet_index(pos: (i8, i8, i8), normal: (i8, i8, i8)) -> usize {
+    let f = FaceKind::from_normal(normal) as usize;
+    let mut i = 0;
+
+    while i < 54 {
+        let face = FACELET_FACES[i / 9];
+        let n = (i % 9) as i8;
+        let p = facelet_position(face, n / 3, n % 3);
+
+        if face as usize == f && p.0 == pos.0 && p.1 == pos.1 && p.2 == pos.2 {
+            return i;
+        }
+        i += 1;
+    }
+
+    panic!("No facelet at position")
+}
+
 /// A row of cubies. Each row has a left, right, and center, though the center
 /// middle row has the turning mechanism instead. For this reason, center is an
 /// Option<&'a Box<dyn Cubie>>.
@@ -71,6 +628,7 @@ impl<'a> Column<'a> {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CornerPosition {
     TopBackLeft,
     TopBackRight,
@@ -82,6 +640,22 @@ pub enum CornerPosition {
     BottomFrontRight,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgePosition {
+    TopBack,
+    TopLeft,
+    TopRight,
+    TopFront,
+    MiddleBackLeft,
+    MiddleBackRight,
+    MiddleFrontLeft,
+    MiddleFrontRight,
+    BottomBack,
+    BottomLeft,
+    BottomRight,
+    BottomFront,
+}
+
 pub enum ColumnPosition {
     BackLeft,
     BackMiddle,
@@ -120,25 +694,28 @@ pub enum RowPosition {
 /// let column = Cube::column(&cube, ColumnPosition::MiddleRight);
 /// let corner = Cube::corner(&cube, CornerPosition::TopBackRight);
 /// ```
+#[derive(Clone)]
 pub struct Cube {
     pub elements: [Box<dyn Cubie>; 26],
 }
 
 #[macro_use]
 macro_rules! initialize_cube_face {
-    ($o:expr, $x:expr) => {
+    ($o:expr, $face:expr) => {{
+        let x = $crate::cube::cube::face_elements($face);
+
         Face::new_from_array([
-            &$o.elements[$x[0]],
-            &$o.elements[$x[1]],
-            &$o.elements[$x[2]],
-            &$o.elements[$x[3]],
-            &$o.elements[$x[4]],
-            &$o.elements[$x[5]],
-            &$o.elements[$x[6]],
-            &$o.elements[$x[7]],
-            &$o.elements[$x[8]],
+            &$o.elements[x[0]],
+            &$o.elements[x[1]],
+            &$o.elements[x[2]],
+            &$o.elements[x[3]],
+            &$o.elements[x[4]],
+            &$o.elements[x[5]],
+            &$o.elements[x[6]],
+            &$o.elements[x[7]],
+            &$o.elements[x[8]],
         ])
-    };
+    }};
 }
 
 impl Cube {
@@ -179,7 +756,7 @@ impl Cube {
                 cubie!("edge"),
                 cubie!("corner"),
                 cubie!("edge"),
-                cubie!("corner"),
+                cubie!("center"),
                 cubie!("edge"),
                 cubie!("corner"),
                 cubie!("edge"),
@@ -188,6 +765,248 @@ impl Cube {
         }
     }
 
+    /// Initializes a solved 3x3 Cube using the standard Western color scheme,
+    /// held with white on top and green in front.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::solved();
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn solved() -> Self {
+        let mut cube = Self::new();
+
+        for (i, element) in cube.elements.iter_mut().enumerate() {
+            let faces = element_faces(i);
+
+            for (face, kind) in element.face_slice_mut().iter_mut().zip(&faces)
+            {
+                face.color = solved_color(*kind);
+            }
+        }
+
+        cube
+    }
+
+    /// Initializes a solved 3x3 Cube and applies each [`Move`] from an
+    /// iterator to it in order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let cube = Cube::from_moves_iter(vec![Move::R, Move::U]);
+    ///
+    /// assert!(!cube.is_solved());
+    /// ```
+    pub fn from_moves_iter<I: IntoIterator<Item = Move>>(moves: I) -> Self {
+        let mut cube = Self::solved();
+
+        for m in moves {
+            cube.apply(m);
+        }
+
+        cube
+    }
+
+    /// Builds a cube piece by piece, giving the colors and orientation of the
+    /// piece at every corner and edge position, and checks that the result is
+    /// a valid state with [`Cube::validate`]. The centers are those of
+    /// [`Cube::solved`].
+    ///
+    /// A piece's colors are listed in the order its position stores its
+    /// faces: the top or bottom face first, then the front or back, then the
+    /// left or right. Corners are then twisted by their orientation, from 0
+    /// to 2 and counted the same way as [`Cube::corner_orientations`], and
+    /// edges are flipped if their orientation is 1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// # use Color::*;
+    /// let corners = [
+    ///     (CornerPosition::TopBackLeft, [White, Blue, Orange], 0),
+    ///     (CornerPosition::TopBackRight, [White, Blue, Red], 0),
+    ///     (CornerPosition::TopFrontLeft, [White, Green, Orange], 0),
+    ///     (CornerPosition::TopFrontRight, [White, Green, Red], 0),
+    ///     (CornerPosition::BottomBackLeft, [Yellow, Blue, Orange], 0),
+    ///     (CornerPosition::BottomBackRight, [Yellow, Blue, Red], 0),
+    ///     (CornerPosition::BottomFrontLeft, [Yellow, Green, Orange], 0),
+    ///     (CornerPosition::BottomFrontRight, [Yellow, Green, Red], 0),
+    /// ];
+    /// let edges = [
+    ///     (EdgePosition::TopBack, [White, Blue], 0),
+    ///     (EdgePosition::TopLeft, [White, Orange], 0),
+    ///     (EdgePosition::TopRight, [White, Red], 0),
+    ///     (EdgePosition::TopFront, [White, Green], 0),
+    ///     (EdgePosition::MiddleBackLeft, [Blue, Orange], 0),
+    ///     (EdgePosition::MiddleBackRight, [Blue, Red], 0),
+    ///     (EdgePosition::MiddleFrontLeft, [Green, Orange], 0),
+    ///     (EdgePosition::MiddleFrontRight, [Green, Red], 0),
+    ///     (EdgePosition::BottomBack, [Yellow, Blue], 0),
+    ///     (EdgePosition::BottomLeft, [Yellow, Orange], 0),
+    ///     (EdgePosition::BottomRight, [Yellow, Red], 0),
+    ///     (EdgePosition::BottomFront, [Yellow, Green], 0),
+    /// ];
+    ///
+    /// assert!(Cube::from_pieces(corners, edges).unwrap().is_solved());
+    /// ```
+    pub fn from_pieces(
+        corners: [(CornerPosition, [Color; 3], u8); 8],
+        edges: [(EdgePosition, [Color; 2], u8); 12],
+    ) -> Result<Self, CubeError> {
+        let mut cube = Self::solved();
+
+        for (pos, colors, orientation) in corners.iter() {
+            if *orientation >= 3 {
+                return Err(CubeError::InvalidOrientation(*orientation));
+            }
+
+            let (x, y, z) = corner_coordinates(*pos as usize);
+            let faces =
+                cube.elements[element_index((x, y, z))].face_slice_mut();
+
+            for (face, color) in faces.iter_mut().zip(colors) {
+                face.color = *color;
+            }
+
+            // Faces are ordered in opposite directions around corners on
+            // opposite sides of the cube
+            if x * y * z > 0 {
+                faces.rotate_right(*orientation as usize);
+            } else {
+                faces.rotate_left(*orientation as usize);
+            }
+        }
+
+        for (pos, colors, orientation) in edges.iter() {
+            if *orientation >= 2 {
+                return Err(CubeError::InvalidOrientation(*orientation));
+            }
+
+            let i = element_index(edge_coordinates(*pos as usize));
+            let faces = cube.elements[i].face_slice_mut();
+
+            for (face, color) in faces.iter_mut().zip(colors) {
+                face.color = *color;
+            }
+            faces.rotate_right(*orientation as usize);
+        }
+
+        cube.validate()?;
+
+        Ok(cube)
+    }
+
+    /// Initializes a cube in the checkerboard pattern, where every face
+    /// alternates between its own color and the opposite color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::checkerboard();
+    ///
+    /// assert!(!cube.is_solved());
+    /// ```
+    pub fn checkerboard() -> Self {
+        Self::from_moves_iter(vec![Move::M2, Move::E2, Move::S2])
+    }
+
+    /// Initializes a cube in the cube in a cube pattern, which looks like a
+    /// smaller cube nested in one corner of the cube.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::cube_in_cube();
+    ///
+    /// assert!(!cube.is_solved());
+    /// ```
+    pub fn cube_in_cube() -> Self {
+        Self::from_moves_iter(vec![
+            Move::F,
+            Move::L,
+            Move::F,
+            Move::U_PRIME,
+            Move::R,
+            Move::U,
+            Move::F2,
+            Move::L2,
+            Move::U_PRIME,
+            Move::L_PRIME,
+            Move::B,
+            Move::D_PRIME,
+            Move::B_PRIME,
+            Move::L2,
+            Move::U,
+        ])
+    }
+
+    /// Initializes a cube in the six spot pattern, where each center is
+    /// surrounded by a different color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::six_spot();
+    ///
+    /// assert!(!cube.is_solved());
+    /// ```
+    pub fn six_spot() -> Self {
+        Self::from_moves_iter(vec![
+            Move::U,
+            Move::D_PRIME,
+            Move::R,
+            Move::L_PRIME,
+            Move::F,
+            Move::B_PRIME,
+            Move::U,
+            Move::D_PRIME,
+        ])
+    }
+
+    /// Scrambles a solved cube and solves it again, returning the scramble
+    /// and the solution. In debug builds the solution is checked to actually
+    /// solve the scramble.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the solver can't find a solution for the scramble.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, solver::*};
+    /// let mut rng = rand::thread_rng();
+    /// let solver = OptimalSolver::new(3);
+    ///
+    /// let (scramble, solution) =
+    ///     Cube::scramble_and_solve(3, &mut rng, &solver);
+    ///
+    /// assert!(solution.len() <= scramble.len());
+    /// ```
+    pub fn scramble_and_solve(
+        len: usize,
+        rng: &mut impl Rng,
+        solver: &dyn Solver,
+    ) -> (Vec<Move>, Vec<Move>) {
+        let mut cube = Self::solved();
+        let scramble = cube.scramble(len, rng);
+
+        let solution = match solver.solve(&cube) {
+            Some(solution) => solution,
+            None => panic!("Solver failed to solve the scramble"),
+        };
+
+        #[cfg(debug_assertions)]
+        {
+            let solved = cube.apply_sequence(&solution).is_solved();
+            debug_assert!(solved, "Solver returned a wrong solution");
+        }
+
+        (scramble, solution)
+    }
+
     /// Returns a corner [`Cubie`] using the cube's underlying element
     /// structure.
     ///
@@ -199,17 +1018,7 @@ impl Cube {
     /// let corner = Cube::corner_raw(&cube, 4);
     /// ```
     pub const fn corner_raw(&self, pos: usize) -> &Box<dyn Cubie> {
-        match pos {
-            0 => &self.elements[0],
-            1 => &self.elements[2],
-            2 => &self.elements[6],
-            3 => &self.elements[8],
-            4 => &self.elements[17],
-            5 => &self.elements[19],
-            6 => &self.elements[23],
-            7 => &self.elements[25],
-            _ => panic!("Exceeded corner count"),
-        }
+        &self.elements[element_index(corner_coordinates(pos))]
     }
 
     /// Returns a corner [`Cubie`] using the [`CornerPosition`] enum.
@@ -256,63 +1065,93 @@ impl Cube {
         ]
     }
 
-    /// Returns a [`Row`] using the cube's underlying element structure.
+    /// Returns an edge [`Cubie`] using the cube's underlying element
+    /// structure.
     ///
     /// # Examples
     /// ```
     /// # use rustytwisty::cube::cube::*;
     /// let cube = Cube::new();
     ///
-    /// let row = Cube::row_raw(&cube, 5);
+    /// let edge = Cube::edge_raw(&cube, 4);
     /// ```
-    pub const fn row_raw(&self, pos: usize) -> Row {
-        match pos {
-            0 => Row {
-                left: &self.elements[0],
-                center: Some(&self.elements[1]),
-                right: &self.elements[2],
-            },
-            1 => Row {
-                left: &self.elements[3],
-                center: Some(&self.elements[4]),
-                right: &self.elements[5],
-            },
-            2 => Row {
-                left: &self.elements[6],
-                center: Some(&self.elements[7]),
-                right: &self.elements[8],
-            },
-            3 => Row {
-                left: &self.elements[9],
-                center: Some(&self.elements[10]),
-                right: &self.elements[11],
-            },
-            4 => Row {
-                left: &self.elements[12],
-                center: None,
-                right: &self.elements[13],
-            },
-            5 => Row {
-                left: &self.elements[14],
-                center: Some(&self.elements[15]),
-                right: &self.elements[16],
-            },
-            6 => Row {
-                left: &self.elements[17],
-                center: Some(&self.elements[18]),
-                right: &self.elements[19],
-            },
-            7 => Row {
-                left: &self.elements[20],
-                center: Some(&self.elements[21]),
-                right: &self.elements[22],
-            },
-            8 => Row {
-                left: &self.elements[23],
-                center: Some(&self.elements[24]),
-                right: &self.elements[25],
-            },
-            _ => panic!("Exceeded row count"),
+    pub const fn edge_raw(&self, pos: usize) -> &Box<dyn Cubie> {
+        &self.elements[element_index(edge_coordinates(pos))]
+    }
+
+    /// Returns an edge [`Cubie`] using the [`EdgePosition`] enum.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::new();
+    ///
+    /// let edge = Cube::edge(&cube, EdgePosition::MiddleFrontLeft);
+    /// ```
+    pub const fn edge(&self, pos: EdgePosition) -> &Box<dyn Cubie> {
+        match pos {
+            EdgePosition::TopBack => Cube::edge_raw(&self, 0),
+            EdgePosition::TopLeft => Cube::edge_raw(&self, 1),
+            EdgePosition::TopRight => Cube::edge_raw(&self, 2),
+            EdgePosition::TopFront => Cube::edge_raw(&self, 3),
+            EdgePosition::MiddleBackLeft => Cube::edge_raw(&self, 4),
+            EdgePosition::MiddleBackRight => Cube::edge_raw(&self, 5),
+            EdgePosition::MiddleFrontLeft => Cube::edge_raw(&self, 6),
+            EdgePosition::MiddleFrontRight => Cube::edge_raw(&self, 7),
+            EdgePosition::BottomBack => Cube::edge_raw(&self, 8),
+            EdgePosition::BottomLeft => Cube::edge_raw(&self, 9),
+            EdgePosition::BottomRight => Cube::edge_raw(&self, 10),
+            EdgePosition::BottomFront => Cube::edge_raw(&self, 11),
+        }
+    }
+
+    /// Returns an array containing all of the cube's edge [`Cubie`] objects.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::new();
+    ///
+    /// let edges = Cube::edges(&cube);
+    /// ```
+    pub const fn edges(&self) -> [&Box<dyn Cubie>; 12] {
+        [
+            Cube::edge_raw(&self, 0),
+            Cube::edge_raw(&self, 1),
+            Cube::edge_raw(&self, 2),
+            Cube::edge_raw(&self, 3),
+            Cube::edge_raw(&self, 4),
+            Cube::edge_raw(&self, 5),
+            Cube::edge_raw(&self, 6),
+            Cube::edge_raw(&self, 7),
+            Cube::edge_raw(&self, 8),
+            Cube::edge_raw(&self, 9),
+            Cube::edge_raw(&self, 10),
+            Cube::edge_raw(&self, 11),
+        ]
+    }
+
+    /// Returns a [`Row`] using the cube's underlying element structure.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::new();
+    ///
+    /// let row = Cube::row_raw(&cube, 5);
+    /// ```
+    pub const fn row_raw(&self, pos: usize) -> Row {
+        if pos >= 9 {
+            panic!("Exceeded row count");
+        }
+
+        let y = 1 - (pos / 3) as i8;
+        let z = (pos % 3) as i8 - 1;
+
+        Row {
+            left: &self.elements[element_index((-1, y, z))],
+            center: self.element_at((0, y, z)),
+            right: &self.elements[element_index((1, y, z))],
         }
     }
 
@@ -372,53 +1211,58 @@ impl Cube {
     /// let column = Cube::column_raw(&cube, 5);
     /// ```
     pub const fn column_raw(&self, pos: usize) -> Column {
-        match pos {
-            0 => Column {
-                top: &self.elements[0],
-                center: Some(&self.elements[9]),
-                bottom: &self.elements[18],
-            },
-            1 => Column {
-                top: &self.elements[1],
-                center: Some(&self.elements[10]),
-                bottom: &self.elements[19],
-            },
-            2 => Column {
-                top: &self.elements[2],
-                center: Some(&self.elements[11]),
-                bottom: &self.elements[20],
-            },
-            3 => Column {
-                top: &self.elements[3],
-                center: Some(&self.elements[12]),
-                bottom: &self.elements[21],
-            },
-            4 => Column {
-                top: &self.elements[4],
-                center: None,
-                bottom: &self.elements[21],
-            },
-            5 => Column {
-                top: &self.elements[5],
-                center: Some(&self.elements[13]),
-                bottom: &self.elements[22],
-            },
-            6 => Column {
-                top: &self.elements[6],
-                center: Some(&self.elements[14]),
-                bottom: &self.elements[23],
-            },
-            7 => Column {
-                top: &self.elements[7],
-                center: Some(&self.elements[15]),
-                bottom: &self.elements[24],
-            },
-            8 => Column {
-                top: &self.elements[8],
-                center: Some(&self.elements[16]),
-                bottom: &self.elements[25],
-            },
-            _ => panic!("Exceeded column count"),
+        if pos >= 9 {
+            panic!("Exceeded column count");
+        }
+
+        let x = (pos % 3) as i8 - 1;
+        let z = (pos / 3) as i8 - 1;
+
+        Column {
+            top: &self.elements[element_index((x, 1, z))],
+            center: self.element_at((x, 0, z)),
+            bottom: &self.elements[element_index((x, -1, z))],
+        }
+    }
+
+    /// Returns the nine elements of a horizontal layer of the cube, where `y`
+    /// is 1 for the top layer, 0 for the middle layer, and -1 for the bottom
+    /// layer. Elements are ordered back to front and then left to right, and
+    /// the middle layer has None in place of the core.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::solved();
+    ///
+    /// let middle = cube.layer(0);
+    ///
+    /// assert!(middle[4].is_none());
+    /// ```
+    pub const fn layer(&self, y: i8) -> [Option<&Box<dyn Cubie>>; 9] {
+        if y < -1 || y > 1 {
+            panic!("Exceeded layer count");
+        }
+
+        [
+            self.element_at((-1, y, -1)),
+            self.element_at((0, y, -1)),
+            self.element_at((1, y, -1)),
+            self.element_at((-1, y, 0)),
+            self.element_at((0, y, 0)),
+            self.element_at((1, y, 0)),
+            self.element_at((-1, y, 1)),
+            self.element_at((0, y, 1)),
+            self.element_at((1, y, 1)),
+        ]
+    }
+
+    /// Returns the element at a set of coordinates, or None for the core.
+    const fn element_at(&self, pos: (i8, i8, i8)) -> Option<&Box<dyn Cubie>> {
+        if pos.0 == 0 && pos.1 == 0 && pos.2 == 0 {
+            None
+        } else {
+            Some(&self.elements[element_index(pos)])
         }
     }
 
@@ -478,62 +1322,4199 @@ impl Cube {
     /// let face = Cube::face(&cube, FaceKind::Right);
     /// ```
     pub const fn face(&self, s: FaceKind) -> Face {
-        match s {
-            FaceKind::Top => {
-                initialize_cube_face!(&self, [0, 1, 2, 3, 4, 5, 6, 7, 8])
-            }
-            FaceKind::Left => {
-                initialize_cube_face!(&self, [0, 3, 6, 9, 12, 14, 17, 20, 23])
-            }
-            FaceKind::Right => {
-                initialize_cube_face!(&self, [2, 5, 8, 11, 13, 16, 19, 22, 25])
-            }
-            FaceKind::Front => {
-                initialize_cube_face!(&self, [6, 7, 8, 14, 15, 16, 23, 24, 25])
+        initialize_cube_face!(&self, s)
+    }
+
+    /// Applies a single [`Move`] to the cube, returning the cube so that calls
+    /// can be chained.
+    ///
+    /// Rotations such as `x` really turn the whole cube, so face moves after
+    /// them always turn whichever face is now in that position. For example,
+    /// `x U x'` turns the face which started in front, the same as `F`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply(Move::R);
+    /// assert!(!cube.is_solved());
+    ///
+    /// cube.apply(Move::U).apply(Move::U_PRIME).apply(Move::R_PRIME);
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn apply(&mut self, m: Move) -> &mut Self {
+        for _ in 0..m.direction.quarter_turns() {
+            self.quarter_turn(m.layer);
+        }
+
+        self
+    }
+
+    /// Applies the same [`Move`] to the cube a number of times in a row,
+    /// returning the cube so that calls can be chained.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply_n(Move::R, 4);
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn apply_n(&mut self, m: Move, n: usize) -> &mut Self {
+        for _ in 0..n {
+            self.apply(m);
+        }
+
+        self
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order, returning the
+    /// cube so that calls can be chained.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+    /// ```
+    pub fn apply_sequence(&mut self, moves: &[Move]) -> &mut Self {
+        for m in moves {
+            self.apply(*m);
+        }
+
+        self
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order, unless the
+    /// sequence is longer than `max_len` moves, in which case the cube is left
+    /// untouched. This guards against sequences from untrusted input being
+    /// too long to apply.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let moves = Move::parse_sequence("R U R' U'").unwrap();
+    ///
+    /// assert!(cube.apply_sequence_bounded(&moves, 4).is_ok());
+    /// assert_eq!(
+    ///     cube.apply_sequence_bounded(&moves, 3),
+    ///     Err(CubeError::SequenceTooLong(4))
+    /// );
+    /// ```
+    pub fn apply_sequence_bounded(
+        &mut self,
+        moves: &[Move],
+        max_len: usize,
+    ) -> Result<(), CubeError> {
+        if moves.len() > max_len {
+            return Err(CubeError::SequenceTooLong(moves.len()));
+        }
+
+        self.apply_sequence(moves);
+
+        Ok(())
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order, and returns a
+    /// [`Replay`] recording the cube's state before and after along with the
+    /// moves, which can be saved and checked later.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let replay =
+    ///     cube.apply_sequence_logged(&Move::parse_sequence("R U").unwrap());
+    ///
+    /// assert_eq!(replay.final_key, cube.key());
+    /// assert!(replay.verify());
+    /// ```
+    pub fn apply_sequence_logged(&mut self, moves: &[Move]) -> Replay {
+        let initial_key = self.key();
+        self.apply_sequence(moves);
+
+        Replay {
+            initial_key,
+            moves: moves.to_vec(),
+            final_key: self.key(),
+        }
+    }
+
+    /// Applies a sequence of moves to the cube after merging or removing any
+    /// redundant moves with [`optimize`], and returns the optimized sequence
+    /// which was applied. The cube ends up in the same state as if the whole
+    /// sequence had been applied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let moves = Move::parse_sequence("R U U' R").unwrap();
+    ///
+    /// assert_eq!(cube.apply_sequence_optimized(&moves), vec![Move::R2]);
+    /// ```
+    pub fn apply_sequence_optimized(&mut self, moves: &[Move]) -> Vec<Move> {
+        let optimized = optimize(moves);
+        self.apply_sequence(&optimized);
+
+        optimized
+    }
+
+    /// Applies a sequence of moves to the cube like
+    /// [`Cube::apply_sequence_optimized`], and returns an [`ApplyReport`]
+    /// measuring how many of the moves were redundant.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let moves = Move::parse_sequence("R U U' R").unwrap();
+    ///
+    /// let report = cube.apply_and_report(&moves);
+    ///
+    /// assert_eq!(report.applied, 4);
+    /// assert_eq!(report.cancelled, 3);
+    /// assert!(!report.solved);
+    /// ```
+    pub fn apply_and_report(&mut self, moves: &[Move]) -> ApplyReport {
+        let optimized = self.apply_sequence_optimized(moves);
+
+        ApplyReport {
+            applied: moves.len(),
+            cancelled: moves.len() - optimized.len(),
+            solved: self.is_solved(),
+        }
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order, calling a hook
+    /// after every move with the move and the resulting state of the cube.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let mut applied = Vec::new();
+    ///
+    /// cube.apply_sequence_with(&[Move::R, Move::U], |m, _| applied.push(*m));
+    ///
+    /// assert_eq!(applied, vec![Move::R, Move::U]);
+    /// ```
+    pub fn apply_sequence_with<F: FnMut(&Move, &Cube)>(
+        &mut self,
+        moves: &[Move],
+        mut hook: F,
+    ) {
+        for m in moves {
+            self.apply(*m);
+            hook(m, self);
+        }
+    }
+
+    /// Applies moves from an iterator one at a time, without collecting them,
+    /// and calls `on_progress` with the number of moves applied so far after
+    /// every `interval` moves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let mut progress = Vec::new();
+    ///
+    /// let moves = std::iter::repeat(Move::R).take(10);
+    /// cube.apply_stream(moves, 4, |applied| progress.push(applied));
+    ///
+    /// assert_eq!(progress, vec![4, 8]);
+    /// ```
+    pub fn apply_stream<I, F>(
+        &mut self,
+        moves: I,
+        interval: usize,
+        mut on_progress: F,
+    ) where
+        I: IntoIterator<Item = Move>,
+        F: FnMut(usize),
+    {
+        assert!(interval > 0, "Progress interval must be positive");
+
+        for (i, m) in moves.into_iter().enumerate() {
+            self.apply(m);
+
+            if (i + 1) % interval == 0 {
+                on_progress(i + 1);
             }
-            FaceKind::Back => {
-                initialize_cube_face!(&self, [0, 1, 2, 9, 10, 11, 17, 18, 19])
+        }
+    }
+
+    /// Returns the state of the cube before a sequence of moves and after each
+    /// of them, without changing the cube.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let cube = Cube::solved();
+    /// let states = cube.trace_sequence(&[Move::R, Move::R_PRIME]);
+    ///
+    /// assert_eq!(states.len(), 3);
+    /// assert!(!states[1].is_solved());
+    /// assert!(states[2].is_solved());
+    /// ```
+    pub fn trace_sequence(&self, moves: &[Move]) -> Vec<Cube> {
+        let mut states = Vec::with_capacity(moves.len() + 1);
+        states.push(self.clone());
+
+        self.clone()
+            .apply_sequence_with(moves, |_, cube| states.push(cube.clone()));
+
+        states
+    }
+
+    /// Scrambles the cube by applying a number of random face moves, and
+    /// returns the moves applied. The same face is never turned twice in a
+    /// row, or turned again with only its opposite face turned in between, as
+    /// in `R L R`, since the two turns of the first face could be merged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let mut cube = Cube::solved();
+    ///
+    /// let scramble = cube.scramble(20, &mut rand::thread_rng());
+    ///
+    /// assert_eq!(scramble.len(), 20);
+    /// ```
+    pub fn scramble(&mut self, len: usize, rng: &mut impl Rng) -> Vec<Move> {
+        let moves: Vec<Move> = ScrambleIter::new(rng).take(len).collect();
+        self.apply_sequence(&moves);
+
+        moves
+    }
+
+    /// Replaces the cube with a random state whose shortest solution is
+    /// exactly `depth` face moves long, and returns a scramble reaching it.
+    ///
+    /// Random scrambles of `depth` moves are generated until one can't be
+    /// solved in fewer moves by an [`OptimalSolver`], so this is only
+    /// practical for small depths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, solver::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// let scramble = cube.scramble_to_depth(2, &mut rand::thread_rng());
+    ///
+    /// assert_eq!(scramble.len(), 2);
+    /// assert_eq!(OptimalSolver::new(2).solve(&cube).unwrap().len(), 2);
+    /// ```
+    pub fn scramble_to_depth(
+        &mut self,
+        depth: u8,
+        rng: &mut impl Rng,
+    ) -> Vec<Move> {
+        let depth = depth as usize;
+
+        loop {
+            let mut cube = Self::solved();
+            let scramble = cube.scramble(depth, rng);
+
+            if depth == 0
+                || OptimalSolver::new(depth - 1).solve(&cube).is_none()
+            {
+                *self = cube;
+
+                return scramble;
             }
-            FaceKind::Bottom => initialize_cube_face!(&self, [
-                17, 18, 19, 20, 21, 22, 23, 24, 25
-            ]),
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cube::cubie::Corner as CornerCubie;
+    /// Returns the shortest sequence of at most `max` face moves which sets up
+    /// a target state from a solved cube, such as a case to practice an
+    /// algorithm on, or None if there isn't one that short.
+    ///
+    /// The setup is the inverse of the target's shortest solution, found by an
+    /// [`OptimalSolver`], so this is only practical for small `max`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut target = Cube::solved();
+    /// target.apply_sequence(&Move::parse_sequence("R U R'").unwrap());
+    ///
+    /// let setup = Cube::setup_for(&target, 3).unwrap();
+    ///
+    /// assert_eq!(setup, Move::parse_sequence("R U R'").unwrap());
+    /// ```
+    pub fn setup_for(target: &Cube, max: u8) -> Option<Vec<Move>> {
+        let solution = OptimalSolver::new(max as usize).solve(target)?;
 
-    #[test]
-    fn cube_init() {
-        let c = Cube::new();
+        Some(solution.iter().rev().map(Move::inverse).collect())
+    }
 
-        assert_eq!(c.elements.len(), 26);
+    /// Returns the shortest solution to the cube like an [`OptimalSolver`],
+    /// or None if one isn't found before the timeout runs out.
+    ///
+    /// The search's clock is checked periodically rather than after every
+    /// state, so it may run slightly longer than the timeout.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// # use std::time::Duration;
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U").unwrap());
+    ///
+    /// let solution = cube.solve_within(Duration::from_secs(10)).unwrap();
+    ///
+    /// assert_eq!(solution, Move::parse_sequence("U' R'").unwrap());
+    /// ```
+    pub fn solve_within(&self, timeout: Duration) -> Option<Vec<Move>> {
+        OptimalSolver::new(20).solve_until(self, Instant::now() + timeout)
     }
 
-    #[test]
-    fn get_face_array() {
-        let c = Cube::new();
+    /// Returns a solution to the cube of at most `max_depth` moves found with
+    /// Kociemba's two phase algorithm, which is much faster than an optimal
+    /// search but rarely finds the shortest solution. See
+    /// [`TwoPhaseSolver`](crate::cube::kociemba::TwoPhaseSolver), which should
+    /// be used instead when solving many cubes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U F' L2 D B'").unwrap());
+    ///
+    /// let solution = cube.solve_two_phase(22).unwrap();
+    /// cube.apply_sequence(&solution);
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    #[cfg(feature = "kociemba")]
+    pub fn solve_two_phase(&self, max_depth: usize) -> Option<Vec<Move>> {
+        crate::cube::kociemba::solve_kociemba(self, max_depth)
+    }
 
-        let cf: Face = initialize_cube_face!(c, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    /// Scrambles the cube like [`Cube::scramble`], but with each face turned
+    /// in proportion to its weight, and returns the moves applied. Faces with
+    /// no weight are never turned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scramble runs out of faces to turn, which happens when
+    /// fewer than two faces have any weight and more than one move is asked
+    /// for, or only two opposite faces do and more than two moves are.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*, scramble::*};
+    /// let mut cube = Cube::solved();
+    /// let weights = MoveWeights::uniform().with_weight(Layer::U, 5);
+    ///
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// let scramble = cube.scramble_biased(20, &weights, &mut rng);
+    ///
+    /// assert_eq!(scramble.len(), 20);
+    /// ```
+    pub fn scramble_biased(
+        &mut self,
+        len: usize,
+        weights: &MoveWeights,
+        rng: &mut impl Rng,
+    ) -> Vec<Move> {
+        let mut moves: Vec<Move> = Vec::with_capacity(len);
 
-        assert_eq!(cf.elements.len(), 9);
-        let cubie: &Box<dyn Cubie> = &c.elements[0];
-        let cornercubie: &CornerCubie =
-            match cubie.as_any().downcast_ref::<CornerCubie>() {
-                Some(i) => i,
-                None => panic!("cubie isn't a CornerCubie!!"),
-            };
+        while moves.len() < len {
+            let mut previous = moves.iter().rev().map(|m| m.layer);
+            let (last, before_last) = (previous.next(), previous.next());
 
-        let cubie2: &Box<dyn Cubie> = cf.elements[0];
-        let cornercubie2: &CornerCubie =
-            match cubie2.as_any().downcast_ref::<CornerCubie>() {
-                Some(i) => i,
-                None => panic!("cubie2 isn't a CornerCubie!!"),
+            let layer = match weights.pick(last, before_last, rng) {
+                Some(layer) => layer,
+                None => panic!("No face left to turn in a biased scramble"),
             };
 
-        assert_eq!(cornercubie.faces, cornercubie2.faces);
+            let direction = DIRECTIONS[rng.gen_range(0, DIRECTIONS.len())];
+            moves.push(Move::new(layer, direction));
+        }
+
+        self.apply_sequence(&moves);
+
+        moves
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order, except that
+    /// each move is replaced by a different random face move with probability
+    /// `noise_prob`, and returns the moves actually applied. This simulates
+    /// mistakes while executing a sequence, for testing how solvers cope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `noise_prob` isn't between 0 and 1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let moves = Move::parse_sequence("R U R' U'").unwrap();
+    ///
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// let applied = cube.apply_with_noise(&moves, 0.1, &mut rng);
+    ///
+    /// assert_eq!(applied.len(), moves.len());
+    /// ```
+    pub fn apply_with_noise(
+        &mut self,
+        moves: &[Move],
+        noise_prob: f64,
+        rng: &mut impl Rng,
+    ) -> Vec<Move> {
+        let mut applied = Vec::with_capacity(moves.len());
+
+        for m in moves {
+            let mut m = *m;
+
+            if rng.gen_bool(noise_prob) {
+                let others: Vec<Move> =
+                    Move::all_basic().filter(|other| *other != m).collect();
+                m = others[rng.gen_range(0, others.len())];
+            }
+
+            self.apply(m);
+            applied.push(m);
+        }
+
+        applied
+    }
+
+    /// Returns the 18 states one face move away from the cube, each paired with
+    /// the move which reaches it, in the order of [`Move::all_basic`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let successors = Cube::solved().successors();
+    ///
+    /// assert_eq!(successors.len(), 18);
+    /// assert_eq!(successors[0].0, Move::U);
+    /// ```
+    pub fn successors(&self) -> Vec<(Move, Cube)> {
+        Move::all_basic()
+            .map(|m| {
+                let mut next = self.clone();
+                next.apply(m);
+
+                (m, next)
+            })
+            .collect()
+    }
+
+    /// Returns the fewest face moves which turn the cube into another, or None
+    /// if it takes more than `max` moves.
+    ///
+    /// States are searched breadth first from both cubes at once, so the
+    /// search only needs to reach about half of `max` moves deep from each.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let cube = Cube::solved();
+    /// let mut other = cube.clone();
+    /// other.apply_sequence(&Move::parse_sequence("R U R'").unwrap());
+    ///
+    /// assert_eq!(cube.distance_to(&other, 4), Some(3));
+    /// assert_eq!(cube.distance_to(&other, 2), None);
+    /// ```
+    pub fn distance_to(&self, other: &Cube, max: u8) -> Option<u8> {
+        if self.key() == other.key() {
+            return Some(0);
+        }
+
+        let mut near = vec![self.clone()];
+        let mut near_seen: HashSet<[u8; 27]> = HashSet::new();
+        near_seen.insert(self.key());
+
+        let mut far = vec![other.clone()];
+        let mut far_seen: HashSet<[u8; 27]> = HashSet::new();
+        far_seen.insert(other.key());
+
+        for distance in 1..=max {
+            // Grow whichever side has the smaller frontier
+            if near.len() > far.len() {
+                std::mem::swap(&mut near, &mut far);
+                std::mem::swap(&mut near_seen, &mut far_seen);
+            }
+
+            let mut frontier = Vec::new();
+            for cube in near.iter() {
+                for (_, next) in cube.successors() {
+                    let key = next.key();
+
+                    if far_seen.contains(&key) {
+                        return Some(distance);
+                    }
+                    if near_seen.insert(key) {
+                        frontier.push(next);
+                    }
+                }
+            }
+            near = frontier;
+        }
+
+        None
+    }
+
+    /// Applies a single random face move to the cube, chosen uniformly from
+    /// the 18 basic moves, and returns the move applied. Unlike
+    /// [`Cube::scramble`], nothing stops the move from undoing an earlier one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let mut cube = Cube::solved();
+    ///
+    /// let m = cube.apply_random_move(&mut rand::thread_rng());
+    /// cube.apply(m.inverse());
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn apply_random_move(&mut self, rng: &mut impl Rng) -> Move {
+        let layer = FACE_LAYERS[rng.gen_range(0, FACE_LAYERS.len())];
+        let direction = DIRECTIONS[rng.gen_range(0, DIRECTIONS.len())];
+        let m = Move::new(layer, direction);
+
+        self.apply(m);
+
+        m
+    }
+
+    /// Turns a face of the cube a quarter turn clockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.rotate_face_cw(FaceKind::Front);
+    /// ```
+    pub fn rotate_face_cw(&mut self, face: FaceKind) -> &mut Self {
+        self.apply(Move::new(face.layer(), Direction::Clockwise))
+    }
+
+    /// Turns a face of the cube a quarter turn counter-clockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.rotate_face_ccw(FaceKind::Front);
+    /// ```
+    pub fn rotate_face_ccw(&mut self, face: FaceKind) -> &mut Self {
+        self.apply(Move::new(face.layer(), Direction::CounterClockwise))
+    }
+
+    /// Turns whichever face of the cube has a center of the given color, so
+    /// moves can be described by color rather than by position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.turn_color_face(Color::White, Direction::Clockwise).unwrap();
+    ///
+    /// let mut expected = Cube::solved();
+    /// expected.apply(Move::U);
+    ///
+    /// assert_eq!(cube.facelets(), expected.facelets());
+    /// ```
+    pub fn turn_color_face(
+        &mut self,
+        color: Color,
+        direction: Direction,
+    ) -> Result<(), CubeError> {
+        let face = self
+            .face_of_color(color)
+            .ok_or(CubeError::MissingCenter(color))?;
+
+        self.apply(Move::new(face.layer(), direction));
+
+        Ok(())
+    }
+
+    /// Turns whichever face of the cube has a center of the given color
+    /// clockwise, like [`Cube::rotate_face_cw`] for a face named by its color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let mut cube = Cube::solved();
+    /// cube.rotate_color_face_cw(Color::Green).unwrap();
+    ///
+    /// let mut expected = Cube::solved();
+    /// expected.rotate_face_cw(FaceKind::Front);
+    ///
+    /// assert_eq!(cube.facelets(), expected.facelets());
+    /// ```
+    pub fn rotate_color_face_cw(
+        &mut self,
+        color: Color,
+    ) -> Result<(), CubeError> {
+        self.turn_color_face(color, Direction::Clockwise)
+    }
+
+    /// Turns a layer of the cube clockwise a number of quarter turns, as seen
+    /// looking at a face, where layers are counted inwards from that face
+    /// starting at 0. Both the layer and the quarter turns are checked, so
+    /// this is safe to drive from untrusted input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CubeError::InvalidLayer`] if the layer is deeper than the
+    /// cube, or [`CubeError::InvalidQuarterTurns`] if the quarter turns aren't
+    /// from 1 to 3. The cube is left untouched on an error.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.turn_checked(FaceKind::Top, 2, 1).unwrap();
+    ///
+    /// let mut expected = Cube::solved();
+    /// expected.apply(Move::D_PRIME);
+    ///
+    /// assert_eq!(cube.facelets(), expected.facelets());
+    /// assert_eq!(
+    ///     cube.turn_checked(FaceKind::Top, 3, 1),
+    ///     Err(CubeError::InvalidLayer(3))
+    /// );
+    /// ```
+    pub fn turn_checked(
+        &mut self,
+        face: FaceKind,
+        layer: usize,
+        quarter_turns: u8,
+    ) -> Result<(), CubeError> {
+        if !(1..=3).contains(&quarter_turns) {
+            return Err(CubeError::InvalidQuarterTurns(quarter_turns));
+        }
+
+        let m = Direction::from_quarter_turns(quarter_turns).and_then(|d| {
+            Move::from_layer_range(face.layer(), layer + 1, layer + 1, d)
+        });
+
+        match m {
+            Some(m) => {
+                self.apply(m);
+
+                Ok(())
+            }
+            None => Err(CubeError::InvalidLayer(layer)),
+        }
+    }
+
+    /// Returns the colors of the nine stickers on a face of the cube, in the
+    /// same order as the elements returned by [`Cube::face`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved();
+    ///
+    /// assert_eq!(cube.face_colors(FaceKind::Top), [Color::White; 9]);
+    /// ```
+    pub fn face_colors(&self, face: FaceKind) -> [Color; 9] {
+        let mut colors = [Color::Uninit; 9];
+        let mut n = 0;
+
+        for (i, element) in self.elements.iter().enumerate() {
+            if let Some(k) = element_faces(i).iter().position(|f| *f == face) {
+                colors[n] = element.face_slice()[k].color;
+                n += 1;
+            }
+        }
+
+        colors
+    }
+
+    /// Returns how many stickers of each [`Color`] the cube has, indexed by
+    /// the color's position in the enum. Every valid cube has nine stickers of
+    /// each real color and none which are uninitialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved();
+    /// let histogram = cube.color_histogram();
+    ///
+    /// assert_eq!(histogram[Color::White as usize], 9);
+    /// assert_eq!(histogram[Color::Uninit as usize], 0);
+    /// ```
+    pub fn color_histogram(&self) -> [usize; 7] {
+        let mut histogram = [0; 7];
+
+        for element in self.elements.iter() {
+            for face in element.face_slice() {
+                histogram[face.color as usize] += 1;
+            }
+        }
+
+        histogram
+    }
+
+    /// Changes the color of every sticker of one color on the cube, including
+    /// the center, to another color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.recolor(Color::White, Color::Uninit);
+    ///
+    /// assert_eq!(cube.face_colors(FaceKind::Top), [Color::Uninit; 9]);
+    /// ```
+    pub fn recolor(&mut self, from: Color, to: Color) {
+        for element in self.elements.iter_mut() {
+            for face in element.face_slice_mut() {
+                if face.color == from {
+                    face.color = to;
+                }
+            }
+        }
+    }
+
+    /// Returns a copy of the cube recolored into another color scheme. Every
+    /// sticker the color of a face's center is given that face's color in the
+    /// scheme, so the pattern of the cube is unchanged.
+    ///
+    /// Only the Western scheme has the opposite colors given by
+    /// [`Color::opposite_color`], so cubes in other schemes fail
+    /// [`Cube::validate`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+    ///
+    /// assert!(cube.is_solved());
+    /// assert_eq!(cube.face_colors(FaceKind::Bottom), [Color::Blue; 9]);
+    /// ```
+    pub fn with_scheme(&self, scheme: &ColorScheme) -> Cube {
+        let centers: Vec<Color> = FaceKind::all()
+            .iter()
+            .map(|f| self.color_of_face(*f))
+            .collect();
+        let mut cube = self.clone();
+
+        for element in cube.elements.iter_mut() {
+            for face in element.face_slice_mut() {
+                if let Some(i) = centers.iter().position(|c| *c == face.color) {
+                    face.color = scheme.colors[i];
+                }
+            }
+        }
+
+        cube
+    }
+
+    /// Returns true if both cubes have their pieces in the same places and
+    /// orientations relative to their own centers, whatever colors they are.
+    /// Two cubes in different color schemes given the same scramble are
+    /// permutations of each other.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let moves = Move::parse_sequence("R U F'").unwrap();
+    ///
+    /// let mut western = Cube::solved();
+    /// western.apply_sequence(&moves);
+    ///
+    /// let mut japanese = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+    /// japanese.apply_sequence(&moves);
+    ///
+    /// assert!(western.is_permutation_of(&japanese));
+    /// ```
+    pub fn is_permutation_of(&self, other: &Cube) -> bool {
+        self.with_scheme(&ColorScheme::WESTERN).facelets()
+            == other.with_scheme(&ColorScheme::WESTERN).facelets()
+    }
+
+    /// Returns the solved cube with the same center colors as this cube, which
+    /// is the state a solver should aim for whatever the cube's color scheme
+    /// or orientation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+    /// cube.apply_sequence(&Move::parse_sequence("R U").unwrap());
+    ///
+    /// let target = cube.target_solved();
+    /// cube.apply_sequence(&Move::parse_sequence("U' R'").unwrap());
+    ///
+    /// assert_eq!(cube.facelets(), target.facelets());
+    /// ```
+    pub fn target_solved(&self) -> Cube {
+        let mut colors = [Color::Uninit; 6];
+
+        for (color, face) in colors.iter_mut().zip(FaceKind::all().iter()) {
+            *color = self.color_of_face(*face);
+        }
+
+        Cube::solved().with_scheme(&ColorScheme::new(colors))
+    }
+
+    /// Returns the colors of all 54 stickers of the cube as facelets: the up,
+    /// right, front, down, left, and back faces in turn, each read in rows as
+    /// laid out on a flattened net.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved();
+    /// let facelets = cube.facelets();
+    ///
+    /// assert_eq!(facelets[0], Color::White);
+    /// assert_eq!(facelets[53], Color::Blue);
+    /// ```
+    pub fn facelets(&self) -> [Color; 54] {
+        let mut facelets = [Color::Uninit; 54];
+
+        for (f, face) in FACELET_FACES.iter().enumerate() {
+            for (n, index) in face_sticker_order(*face).iter().enumerate() {
+                let pos = element_position(*index);
+
+                facelets[f * 9 + n] = self.sticker_color(pos, *face);
+            }
+        }
+
+        facelets
+    }
+
+    /// Returns a snapshot of the state of the cube, which can be restored with
+    /// [`Cube::restore`]. Snapshots only hold the colors of the stickers, so
+    /// they're much cheaper to keep than clones of the cube.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// let snapshot = cube.snapshot();
+    ///
+    /// cube.apply(Move::R);
+    /// cube.restore(&snapshot);
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn snapshot(&self) -> CubeSnapshot {
+        CubeSnapshot {
+            facelets: self.facelets(),
+        }
+    }
+
+    /// Returns the cube to the state it was in when a snapshot was taken.
+    pub fn restore(&mut self, snapshot: &CubeSnapshot) {
+        self.set_facelets(&snapshot.facelets);
+    }
+
+    /// Returns the cube's state in a compact encoding: its
+    /// [facelets](Cube::facelets) packed two to a byte, the first in the high
+    /// four bits. Equal states always have equal keys, so keys can be compared
+    /// or hashed in place of cubes.
+    ///
+    /// A key is built straight from the cubies, without going through
+    /// [`Cube::facelets`]. Comparing it against [`SOLVED_KEY`] checks that a
+    /// cube is solved in the standard orientation. Unlike [`Cube::is_solved`],
+    /// it doesn't accept solved cubes which have been rotated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.key(), SOLVED_KEY);
+    ///
+    /// cube.apply(Move::R);
+    /// assert_ne!(cube.key(), SOLVED_KEY);
+    /// ```
+    pub fn key(&self) -> [u8; 27] {
+        let mut key = [0; 27];
+
+        for (element, facelets) in self.elements.iter().zip(&ELEMENT_FACELETS) {
+            for (face, i) in element.face_slice().iter().zip(facelets) {
+                key[i / 2] |= (face.color as u8) << key_shift(*i);
+            }
+        }
+
+        key
+    }
+
+    /// Writes the cube to a writer in a compact binary encoding, which
+    /// [`Cube::from_reader`] reads back. The encoding is the cube's
+    /// [key](Cube::key), so every cube takes 27 bytes and a stream of cubes
+    /// can be written one after another.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::R);
+    ///
+    /// let mut bytes = Vec::new();
+    /// cube.to_writer(&mut bytes).unwrap();
+    ///
+    /// let read = Cube::from_reader(&bytes[..]).unwrap();
+    ///
+    /// assert_eq!(bytes.len(), 27);
+    /// assert_eq!(read.facelets(), cube.facelets());
+    /// ```
+    pub fn to_writer<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.key())
+    }
+
+    /// Reads a cube written by [`Cube::to_writer`] from a reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader fails or ends before a whole cube is
+    /// read, or an error of kind [`io::ErrorKind::InvalidData`] if the bytes
+    /// read don't encode a cube.
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Cube> {
+        let mut bytes = [0; 27];
+        reader.read_exact(&mut bytes)?;
+
+        let mut facelets = [Color::Uninit; 54];
+
+        for (pair, byte) in facelets.chunks_mut(2).zip(bytes.iter()) {
+            for (color, nibble) in pair.iter_mut().zip(&[byte >> 4, byte & 15])
+            {
+                *color = Color::from_index(*nibble).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid color")
+                })?;
+            }
+        }
+
+        let mut cube = Cube::solved();
+        cube.set_facelets(&facelets);
+
+        Ok(cube)
+    }
+
+    /// Sets the colors of all 54 stickers of the cube from facelets in the
+    /// same order as [`Cube::facelets`].
+    pub(crate) fn set_facelets(&mut self, facelets: &[Color; 54]) {
+        for (f, face) in FACELET_FACES.iter().enumerate() {
+            for (n, index) in face_sticker_order(*face).iter().enumerate() {
+                if let Some(k) =
+                    element_faces(*index).iter().position(|f| f == face)
+                {
+                    self.elements[*index].face_slice_mut()[k].color =
+                        facelets[f * 9 + n];
+                }
+            }
+        }
+    }
+
+    /// Returns the facelets of the cube as a string of face letters, in the
+    /// URFDLB format used by Kociemba's two-phase solver. Each sticker is
+    /// labelled with the face whose center has the same color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::R);
+    ///
+    /// assert_eq!(
+    ///     cube.to_kociemba(),
+    ///     "UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB"
+    /// );
+    /// ```
+    pub fn to_kociemba(&self) -> String {
+        self.facelets()
+            .iter()
+            .map(|color| match self.face_of_color(*color) {
+                Some(face) => face.layer().notation(),
+                None => "?",
+            })
+            .collect()
+    }
+
+    /// Returns the facelets of the cube as a string of color letters, in the
+    /// same order as [`Cube::to_kociemba`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::solved();
+    ///
+    /// assert!(cube.to_color_string().starts_with("WWWWWWWWWRRR"));
+    /// ```
+    pub fn to_color_string(&self) -> String {
+        self.facelets().iter().map(Color::letter).collect()
+    }
+
+    /// Returns the index of the first element whose faces differ between two
+    /// cubes, or None if the cubes are the same.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::F);
+    ///
+    /// assert_eq!(cube.first_difference(&Cube::solved()), Some(6));
+    /// ```
+    pub fn first_difference(&self, other: &Cube) -> Option<usize> {
+        self.elements
+            .iter()
+            .zip(other.elements.iter())
+            .position(|(a, b)| a.face_slice() != b.face_slice())
+    }
+
+    /// Returns true if every face of the cube is a single color.
+    ///
+    /// While the centers are in the standard orientation, each sticker is
+    /// compared against [`SOLVED_KEY`], stopping at the first which doesn't
+    /// match, so most unsolved cubes are rejected after reading a single
+    /// cubie. Otherwise every face is scanned, so rotated and recolored cubes
+    /// are still recognized as solved.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// let cube = Cube::solved();
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn is_solved(&self) -> bool {
+        let standard = FaceKind::all()
+            .iter()
+            .all(|face| self.color_of_face(*face) == solved_color(*face));
+
+        if standard {
+            return self.elements.iter().zip(&ELEMENT_FACELETS).all(
+                |(element, facelets)| {
+                    element.face_slice().iter().zip(facelets).all(
+                        |(face, i)| {
+                            let solved =
+                                SOLVED_KEY[i / 2] >> key_shift(*i) & 15;
+
+                            face.color as u8 == solved
+                        },
+                    )
+                },
+            );
+        }
+
+        FaceKind::all().iter().all(|face| {
+            let colors = self.face_colors(*face);
+
+            colors.iter().all(|c| *c == colors[0])
+        })
+    }
+
+    /// Rotates the whole cube so that its centers match the standard Western
+    /// color scheme, with white on top and green in front, and returns the
+    /// rotations used. Only the cube's orientation changes, not its state.
+    ///
+    /// If the cube has no white or green center, it is rotated as far as
+    /// possible and the rotations applied so far are returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::X);
+    ///
+    /// let rotations = cube.normalize_orientation();
+    ///
+    /// assert_eq!(rotations, vec![Move::X_PRIME]);
+    /// assert_eq!(cube.face_colors(FaceKind::Top)[4], Color::White);
+    /// ```
+    pub fn normalize_orientation(&mut self) -> Vec<Move> {
+        let mut rotations = Vec::new();
+
+        let top = match self.face_of_color(Color::White) {
+            Some(FaceKind::Top) | None => None,
+            Some(FaceKind::Bottom) => Some(Move::X2),
+            Some(FaceKind::Front) => Some(Move::X),
+            Some(FaceKind::Back) => Some(Move::X_PRIME),
+            Some(FaceKind::Left) => Some(Move::Z),
+            Some(FaceKind::Right) => Some(Move::Z_PRIME),
+        };
+        if let Some(m) = top {
+            self.apply(m);
+            rotations.push(m);
+        }
+
+        let front = match self.face_of_color(Color::Green) {
+            Some(FaceKind::Right) => Some(Move::Y),
+            Some(FaceKind::Left) => Some(Move::Y_PRIME),
+            Some(FaceKind::Back) => Some(Move::Y2),
+            _ => None,
+        };
+        if let Some(m) = front {
+            self.apply(m);
+            rotations.push(m);
+        }
+
+        rotations
+    }
+
+    /// Rotates the whole cube so that the center of a color ends up on a face,
+    /// and returns the rotations used. At most one rotation is ever needed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// let rotations =
+    ///     cube.rotate_to_put_color_on(FaceKind::Front, Color::Red);
+    ///
+    /// assert_eq!(rotations, Ok(vec![Move::Y]));
+    /// assert_eq!(cube.face_colors(FaceKind::Front)[4], Color::Red);
+    /// ```
+    pub fn rotate_to_put_color_on(
+        &mut self,
+        face: FaceKind,
+        color: Color,
+    ) -> Result<Vec<Move>, CubeError> {
+        let from = self
+            .face_of_color(color)
+            .ok_or(CubeError::MissingCenter(color))?;
+        if from == face {
+            return Ok(Vec::new());
+        }
+
+        let rotation = [Layer::X, Layer::Y, Layer::Z]
+            .iter()
+            .flat_map(|layer| {
+                DIRECTIONS.iter().map(move |d| Move::new(*layer, *d))
+            })
+            .find(|m| {
+                let mut normal = from.normal();
+                for _ in 0..m.direction.quarter_turns() {
+                    normal = rotate_position(m.layer, normal);
+                }
+
+                normal == face.normal()
+            })
+            .expect("every face is one rotation away from every other");
+        self.apply(rotation);
+
+        Ok(vec![rotation])
+    }
+
+    /// Returns the number of first two layers (F2L) corner-edge pairs which are
+    /// placed and oriented correctly, relative to the centers. The first two
+    /// layers are counted from the bottom face, as in CFOP.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.f2l_pairs_solved(), 4);
+    ///
+    /// cube.apply(Move::R);
+    /// assert_eq!(cube.f2l_pairs_solved(), 2);
+    /// ```
+    pub fn f2l_pairs_solved(&self) -> u8 {
+        let mut solved = 0;
+
+        for (x, z) in &[(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let corner = element_index((*x, -1, *z));
+            let edge = element_index((*x, 0, *z));
+
+            if self.element_solved(corner) && self.element_solved(edge) {
+                solved += 1;
+            }
+        }
+
+        solved
+    }
+
+    /// Returns true if the first two layers are solved, counted from the
+    /// bottom face as in CFOP: the bottom cross and all four F2L pairs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply(Move::U);
+    /// assert!(cube.f2l_solved());
+    ///
+    /// cube.apply(Move::R);
+    /// assert!(!cube.f2l_solved());
+    /// ```
+    pub fn f2l_solved(&self) -> bool {
+        self.is_cross_solved(FaceKind::Bottom) && self.f2l_pairs_solved() == 4
+    }
+
+    /// Returns true if the first two layers are solved and the last layer is
+    /// oriented, so that the whole top face is its center's color. This is
+    /// the stage reached after OLL in CFOP.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply(Move::U);
+    /// assert!(cube.oll_solved());
+    /// ```
+    pub fn oll_solved(&self) -> bool {
+        let top = self.face_colors(FaceKind::Top);
+
+        self.f2l_solved() && top.iter().all(|c| *c == top[4])
+    }
+
+    /// Returns true if the last layer is permuted after OLL, which is the last
+    /// stage of CFOP, so this is the same as [`Cube::is_solved`].
+    pub fn pll_solved(&self) -> bool {
+        self.is_solved()
+    }
+
+    /// Returns true if the four edges around the center of a face are solved,
+    /// forming a cross which matches the centers of the faces next to it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::U);
+    ///
+    /// assert!(cube.is_cross_solved(FaceKind::Bottom));
+    /// assert!(!cube.is_cross_solved(FaceKind::Top));
+    /// ```
+    pub fn is_cross_solved(&self, on: FaceKind) -> bool {
+        cross_positions(on)
+            .iter()
+            .all(|pos| self.element_solved(element_index(*pos)))
+    }
+
+    /// Returns a shortest sequence of face moves which solves the cross on a
+    /// face, so that [`Cube::is_cross_solved`] is true for it afterwards. The
+    /// rest of the cube is ignored.
+    ///
+    /// The search only follows the four cross edges, and is guided by how far
+    /// each edge is from its place, so it's quick even though a cross can take
+    /// up to eight moves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cube is missing one of the cross edges.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U F' D2 L").unwrap());
+    ///
+    /// let cross = cube.solve_cross(FaceKind::Bottom);
+    /// cube.apply_sequence(&cross);
+    ///
+    /// assert!(cube.is_cross_solved(FaceKind::Bottom));
+    /// ```
+    pub fn solve_cross(&self, on: FaceKind) -> Vec<Move> {
+        let on_color = self.color_of_face(on);
+
+        // Each cross edge is followed by the position and direction of its
+        // sticker matching the cross face
+        let mut start = [((0, 0, 0), (0, 0, 0)); 4];
+        let mut goal = start;
+
+        for (k, home) in cross_positions(on).iter().enumerate() {
+            let side = FaceKind::from_normal((
+                home.0 - on.normal().0,
+                home.1 - on.normal().1,
+                home.2 - on.normal().2,
+            ));
+            let colors = [on_color, self.color_of_face(side)];
+
+            let index = (0..self.elements.len())
+                .find(|i| {
+                    let faces = self.elements[*i].face_slice();
+
+                    faces.len() == 2
+                        && colors
+                            .iter()
+                            .all(|c| faces.iter().any(|f| f.color == *c))
+                })
+                .expect("Cube is missing a cross edge");
+            let sticker = self.elements[index]
+                .face_slice()
+                .iter()
+                .position(|f| f.color == on_color)
+                .unwrap();
+
+            start[k] = (
+                element_position(index),
+                element_faces(index)[sticker].normal(),
+            );
+            goal[k] = (*home, on.normal());
+        }
+
+        let distances: Vec<[u8; 729]> =
+            goal.iter().map(|edge| edge_distances(*edge)).collect();
+        let mut moves = Vec::new();
+
+        for depth in 0.. {
+            if search_cross(start, depth, &distances, &mut moves) {
+                break;
+            }
+        }
+
+        moves
+    }
+
+    /// Returns true if every edge is oriented, regardless of where the edges
+    /// are placed. Edges are oriented relative to the front and back faces, so
+    /// this is true exactly when the edges can be solved without quarter turns
+    /// of F or B, the first subgroup of Thistlethwaite's algorithm.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply(Move::R);
+    /// assert!(cube.edges_oriented());
+    ///
+    /// cube.apply(Move::F);
+    /// assert!(!cube.edges_oriented());
+    /// ```
+    pub fn edges_oriented(&self) -> bool {
+        self.edge_orientations().iter().all(|o| *o == 0)
+    }
+
+    /// Returns the orientation of the edge in each position, in the same order
+    /// as [`Cube::edge_raw`]: 0 if the edge is oriented as described by
+    /// [`Cube::edges_oriented`], or 1 if it's flipped. Orientations are
+    /// relative to the centers, so a solved cube is all zeros however it's
+    /// rotated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.edge_orientations(), [0; 12]);
+    ///
+    /// cube.apply(Move::F);
+    /// assert_eq!(cube.edge_orientations().iter().sum::<u8>(), 4);
+    /// ```
+    pub fn edge_orientations(&self) -> [u8; 12] {
+        let ud = [
+            self.color_of_face(FaceKind::Top),
+            self.color_of_face(FaceKind::Bottom),
+        ];
+        let fb = [
+            self.color_of_face(FaceKind::Front),
+            self.color_of_face(FaceKind::Back),
+        ];
+        let mut orientations = [0; 12];
+
+        // The first face of each element is its top or bottom face, or its
+        // front or back face for edges in the middle layer.
+        let edges = (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() == 2)
+            .map(|i| self.elements[i].face_slice());
+
+        for (orientation, faces) in orientations.iter_mut().zip(edges) {
+            let oriented = ud.contains(&faces[0].color)
+                || (!ud.contains(&faces[1].color)
+                    && fb.contains(&faces[0].color));
+
+            *orientation = if oriented { 0 } else { 1 };
+        }
+
+        orientations
+    }
+
+    /// Returns true if every corner is oriented, regardless of where the
+    /// corners are placed. A corner is oriented when its top or bottom colored
+    /// sticker faces the top or bottom of the cube.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    ///
+    /// cube.apply(Move::U);
+    /// assert!(cube.corners_oriented());
+    ///
+    /// cube.apply(Move::R);
+    /// assert!(!cube.corners_oriented());
+    /// ```
+    pub fn corners_oriented(&self) -> bool {
+        self.corner_orientations().iter().all(|o| *o == 0)
+    }
+
+    /// Returns the orientation of the corner in each position, in the same
+    /// order as [`Cube::corner_raw`]: the number of times the corner has been
+    /// twisted clockwise from having its top or bottom colored sticker facing
+    /// the top or bottom of the cube, from 0 to 2. Orientations are relative to
+    /// the centers, so a solved cube is all zeros however it's rotated.
+    ///
+    /// Corners without a top or bottom colored sticker count as oriented.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.corner_orientations(), [0; 8]);
+    ///
+    /// cube.apply(Move::R);
+    /// assert_eq!(cube.corner_orientations(), [0, 2, 0, 1, 0, 1, 0, 2]);
+    /// ```
+    pub fn corner_orientations(&self) -> [u8; 8] {
+        let ud = [
+            self.color_of_face(FaceKind::Top),
+            self.color_of_face(FaceKind::Bottom),
+        ];
+        let mut orientations = [0; 8];
+
+        let corners = (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() == 3)
+            .map(|i| (element_position(i), self.elements[i].face_slice()));
+
+        for (orientation, ((x, y, z), faces)) in
+            orientations.iter_mut().zip(corners)
+        {
+            let k = faces.iter().position(|f| ud.contains(&f.color));
+
+            // Faces are ordered in opposite directions around corners on
+            // opposite sides of the cube
+            *orientation = match k {
+                Some(k) if x * y * z > 0 => k as u8,
+                Some(k) => (3 - k as u8) % 3,
+                None => 0,
+            };
+        }
+
+        orientations
+    }
+
+    /// Returns the number of corners and edges which are in the positions where
+    /// they belong relative to the centers, whether or not they're twisted or
+    /// flipped there. A solved cube has all 20 in place.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.pieces_in_correct_position(), 20);
+    ///
+    /// cube.apply(Move::R);
+    /// assert_eq!(cube.pieces_in_correct_position(), 12);
+    /// ```
+    pub fn pieces_in_correct_position(&self) -> usize {
+        (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() > 1)
+            .filter(|i| self.element_home(*i) == Some(*i))
+            .count()
+    }
+
+    /// Compares how close two cubes are to solved, ordering the closer one as
+    /// greater. Cubes with more [pieces in their correct
+    /// positions](Cube::pieces_in_correct_position) are closer, and ties are
+    /// broken by which has fewer stickers that don't match their face's
+    /// center.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// # use std::cmp::Ordering;
+    /// let mut cube = Cube::solved();
+    /// cube.apply(Move::R);
+    ///
+    /// assert_eq!(cube.compare_progress(&Cube::solved()), Ordering::Less);
+    /// assert_eq!(cube.compare_progress(&cube), Ordering::Equal);
+    /// ```
+    pub fn compare_progress(&self, other: &Cube) -> Ordering {
+        let misplaced = |cube: &Cube| {
+            let facelets = cube.facelets();
+
+            (0..facelets.len())
+                .filter(|i| facelets[*i] != facelets[i / 9 * 9 + 4])
+                .count()
+        };
+
+        self.pieces_in_correct_position()
+            .cmp(&other.pieces_in_correct_position())
+            .then_with(|| misplaced(other).cmp(&misplaced(self)))
+    }
+
+    /// Returns true if the centers of every pair of opposite faces have
+    /// opposite colors, as given by [`Color::opposite_color`]. Any cube in the
+    /// standard color scheme passes, however it's turned or rotated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("x y' M E").unwrap());
+    ///
+    /// assert!(cube.centers_consistent());
+    /// ```
+    pub fn centers_consistent(&self) -> bool {
+        [
+            (FaceKind::Top, FaceKind::Bottom),
+            (FaceKind::Left, FaceKind::Right),
+            (FaceKind::Front, FaceKind::Back),
+        ]
+        .iter()
+        .all(|(a, b)| {
+            self.color_of_face(*a).opposite_color() == self.color_of_face(*b)
+        })
+    }
+
+    /// Checks that the cube is a state which can be reached by turning a solved
+    /// cube, returning the first problem found otherwise.
+    ///
+    /// Every sticker must have a color, the centers must be consistent as in
+    /// [`Cube::centers_consistent`], and every corner and edge must be a
+    /// different piece of the cube. Since turns can't twist a single corner,
+    /// flip a single edge, or swap a single pair of pieces, the corner twists
+    /// and edge flips must each add up to nothing and the corner and edge
+    /// permutations must have the same parity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U F' D2").unwrap());
+    ///
+    /// assert_eq!(cube.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), CubeError> {
+        if !self.elements.iter().all(|e| e.faces_are_complete()) {
+            return Err(CubeError::UninitializedSticker);
+        }
+        if !self.centers_consistent() {
+            return Err(CubeError::InconsistentCenters);
+        }
+
+        let mut homes = [None; 26];
+
+        for (i, element) in self.elements.iter().enumerate() {
+            if element.face_slice().len() == 1 {
+                continue;
+            }
+
+            let home =
+                self.element_home(i).ok_or(CubeError::InvalidPiece(i))?;
+
+            if homes.contains(&Some(home)) {
+                return Err(CubeError::DuplicatePiece(i));
+            }
+            homes[i] = Some(home);
+        }
+
+        if self.corner_orientations().iter().sum::<u8>() % 3 != 0 {
+            return Err(CubeError::TwistedCorner);
+        }
+        if self.edge_orientations().iter().sum::<u8>() % 2 != 0 {
+            return Err(CubeError::FlippedEdge);
+        }
+
+        let parity = |len| {
+            let homes: Vec<usize> = (0..self.elements.len())
+                .filter(|i| element_faces(*i).len() == len)
+                .filter_map(|i| homes[i])
+                .collect();
+
+            permutation_parity(&homes)
+        };
+        if parity(3) != parity(2) {
+            return Err(CubeError::PermutationParity);
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the cube can be solved, which is the same as it passing
+    /// [`Cube::validate`]. This is much cheaper than searching for a solution,
+    /// so it's worth checking before handing a state to a solver.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+    ///
+    /// assert!(cube.is_solvable());
+    /// ```
+    pub fn is_solvable(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Checks that moves behave as they should on a real cube, returning a
+    /// description of the first problem found otherwise: every face and slice
+    /// turn takes four quarter turns to return to where it started, opposite
+    /// faces can be turned in either order, and undoing every basic move in
+    /// reverse order returns to solved.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::cube::*;
+    /// assert_eq!(Cube::self_check(), Ok(()));
+    /// ```
+    pub fn self_check() -> Result<(), String> {
+        let solved = Cube::solved().facelets();
+        let turn = |layer| Move::new(layer, Direction::Clockwise);
+
+        for layer in FACE_LAYERS.iter().chain(&[Layer::M, Layer::E, Layer::S]) {
+            let mut cube = Cube::solved();
+
+            for n in 1..=4 {
+                cube.apply(turn(*layer));
+
+                if (cube.facelets() == solved) != (n == 4) {
+                    return Err(format!(
+                        "{} doesn't take four quarter turns to return to \
+                         solved",
+                        layer.notation()
+                    ));
+                }
+            }
+        }
+
+        for layer in FACE_LAYERS.iter() {
+            let opposite = match layer.opposite() {
+                Some(opposite) => opposite,
+                None => continue,
+            };
+
+            let mut a = Cube::solved();
+            a.apply(turn(*layer)).apply(turn(opposite));
+            let mut b = Cube::solved();
+            b.apply(turn(opposite)).apply(turn(*layer));
+
+            if a.facelets() != b.facelets() {
+                return Err(format!(
+                    "{} and {} don't commute",
+                    layer.notation(),
+                    opposite.notation()
+                ));
+            }
+        }
+
+        let moves: Vec<Move> = Move::all_basic().collect();
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&moves);
+        for m in moves.iter().rev() {
+            cube.apply(m.inverse());
+        }
+
+        if cube.facelets() != solved {
+            return Err(
+                "undoing the basic moves doesn't return to solved".to_string()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Thistlethwaite subgroup the cube is in, the smallest of the
+    /// nested groups of states which can be solved using only its moves.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R2 U2 F2").unwrap());
+    ///
+    /// assert_eq!(cube.subgroup(), Subgroup::G3);
+    /// ```
+    pub fn subgroup(&self) -> Subgroup {
+        if self.is_solved() {
+            return Subgroup::Solved;
+        } else if !self.edges_oriented() {
+            return Subgroup::G0;
+        }
+
+        let mut homes = [0; 26];
+        for (i, home) in homes.iter_mut().enumerate() {
+            match self.element_home(i) {
+                Some(h) => *home = h,
+                None => return Subgroup::G0,
+            }
+        }
+
+        let (x, y, z) = (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() == 2)
+            .map(|i| (element_position(i), element_position(homes[i])))
+            .fold((true, true, true), |(x, y, z), (pos, home)| {
+                (
+                    x && (pos.0 == 0) == (home.0 == 0),
+                    y && (pos.1 == 0) == (home.1 == 0),
+                    z && (pos.2 == 0) == (home.2 == 0),
+                )
+            });
+
+        if !self.corners_oriented() || !y {
+            return Subgroup::G1;
+        }
+
+        let corners: Vec<usize> = (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() == 3)
+            .map(|i| homes[i])
+            .collect();
+        let edges: Vec<usize> = (0..self.elements.len())
+            .filter(|i| element_faces(*i).len() == 2)
+            .map(|i| homes[i])
+            .collect();
+
+        if x && z
+            && half_turn_corner_permutations().contains(&corners)
+            && permutation_parity(&corners) == permutation_parity(&edges)
+        {
+            Subgroup::G3
+        } else {
+            Subgroup::G2
+        }
+    }
+
+    /// Returns the index of the position where the piece at an element
+    /// belongs, which is the position whose faces point towards centers of all
+    /// of the piece's colors. Returns None if there is no such position.
+    fn element_home(&self, index: usize) -> Option<usize> {
+        let faces = self.elements[index].face_slice();
+
+        (0..self.elements.len()).find(|j| {
+            let kinds = element_faces(*j);
+
+            kinds.len() == faces.len()
+                && kinds.iter().all(|kind| {
+                    let color = self.color_of_face(*kind);
+
+                    faces.iter().any(|face| face.color == color)
+                })
+        })
+    }
+
+    /// Returns the color of the center cubie of a face, which is the color the
+    /// face has once the cube is solved.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved();
+    ///
+    /// assert_eq!(cube.color_of_face(FaceKind::Front), Color::Green);
+    /// ```
+    pub fn color_of_face(&self, face: FaceKind) -> Color {
+        self.elements[element_index(face.normal())].face_slice()[0].color
+    }
+
+    /// Returns the face whose center cubie is a given color, or None if no
+    /// center is that color.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*, moves::*};
+    /// let mut cube = Cube::solved();
+    /// assert_eq!(cube.face_of_color(Color::White), Some(FaceKind::Top));
+    ///
+    /// cube.apply(Move::X);
+    /// assert_eq!(cube.face_of_color(Color::White), Some(FaceKind::Back));
+    /// ```
+    pub fn face_of_color(&self, color: Color) -> Option<FaceKind> {
+        FaceKind::all()
+            .iter()
+            .find(|face| self.color_of_face(**face) == color)
+            .copied()
+    }
+
+    /// Returns the color of the sticker at a position which faces towards a
+    /// face of the cube.
+    fn sticker_color(&self, pos: (i8, i8, i8), face: FaceKind) -> Color {
+        let index = element_index(pos);
+
+        match element_faces(index).iter().position(|f| *f == face) {
+            Some(k) => self.elements[index].face_slice()[k].color,
+            None => panic!("Element has no face towards the face"),
+        }
+    }
+
+    /// Returns true if every face of an element matches the center of the cube
+    /// face it points towards.
+    fn element_solved(&self, index: usize) -> bool {
+        let faces = element_faces(index);
+
+        self.elements[index]
+            .face_slice()
+            .iter()
+            .zip(&faces)
+            .all(|(face, kind)| face.color == self.color_of_face(*kind))
+    }
+
+    /// Turns a layer of the cube a single clockwise quarter turn by moving
+    /// every sticker in the layer to its new position.
+    fn quarter_turn(&mut self, layer: Layer) {
+        let mut colors = [[Color::Uninit; 3]; 26];
+
+        for (i, element) in self.elements.iter().enumerate() {
+            for (k, face) in element.face_slice().iter().enumerate() {
+                colors[i][k] = face.color;
+            }
+        }
+
+        let permutation = layer_permutation(layer);
+
+        for (i, from) in colors.iter().enumerate() {
+            if !layer_contains(layer, element_position(i)) {
+                continue;
+            }
+
+            let j = permutation[i];
+            let to = element_faces(j);
+
+            for (k, face) in element_faces(i).iter().enumerate() {
+                let turned = FaceKind::from_normal(rotate_position(
+                    layer,
+                    face.normal(),
+                ));
+                let m = match to.iter().position(|f| *f == turned) {
+                    Some(m) => m,
+                    None => panic!("Turned face not found on element"),
+                };
+
+                self.elements[j].face_slice_mut()[m].color = from[k];
+            }
+        }
+    }
+}
+
+/// Formats the cube as a flattened net of color letters, with the top face
+/// above the left, front, right, and back faces, and the bottom face below.
+///
+/// ```text
+///       W W W
+///       W W W
+///       W W W
+/// O O O G G G R R R B B B
+/// O O O G G G R R R B B B
+/// O O O G G G R R R B B B
+///       Y Y Y
+///       Y Y Y
+///       Y Y Y
+/// ```
+///
+/// If a width is given, every line is padded with the fill character to at
+/// least that width. The net is aligned as a whole by the formatter's
+/// alignment, left by default, so its faces stay lined up.
+impl fmt::Display for Cube {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        format_net(&self.facelets(), f)
+    }
+}
+
+/// Formats the net of a cube with the given facelets, as described for the
+/// [`Display`](fmt::Display) implementation of [`Cube`].
+pub(crate) fn format_net(
+    facelets: &[Color; 54],
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let letters = |c: Color| c.letter().to_string();
+    let width = match f.width() {
+        Some(width) => width,
+        None => return write_net(facelets, f, &letters),
+    };
+
+    let mut net = String::new();
+    write_net(facelets, &mut net, &letters)?;
+
+    let net_width = net.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let padding = width.saturating_sub(net_width);
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill().to_string();
+
+    for line in net.lines() {
+        let after = after + net_width - line.chars().count();
+
+        writeln!(f, "{}{}{}", fill.repeat(before), line, fill.repeat(after))?;
+    }
+
+    Ok(())
+}
+
+impl Cube {
+    /// Returns the net of the cube as formatted by its [`Display`]
+    /// implementation, with every sticker colored in a palette using ANSI
+    /// escape codes, for printing to terminals with 24-bit color.
+    ///
+    /// [`Display`]: fmt::Display
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let cube = Cube::solved();
+    ///
+    /// println!("{}", cube.to_ansi_string(PaletteScheme::Standard));
+    /// ```
+    pub fn to_ansi_string(&self, scheme: PaletteScheme) -> String {
+        ansi_net(&self.facelets(), scheme)
+    }
+
+    /// Returns the net of the cube as a grid of stickers, laid out the same way
+    /// as by its [`Display`] implementation: the up face above the left,
+    /// front, right, and back faces, with the down face below. Cells of the
+    /// grid outside of the net are None.
+    ///
+    /// [`Display`]: fmt::Display
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let grid = Cube::solved().net_grid();
+    ///
+    /// assert_eq!(grid[0][0], None);
+    /// assert_eq!(grid[0][3], Some(Color::White));
+    /// assert_eq!(grid[4][1], Some(Color::Orange));
+    /// ```
+    pub fn net_grid(&self) -> [[Option<Color>; 12]; 9] {
+        let mut grid = [[None; 12]; 9];
+
+        for sticker in self.unfolded_layout() {
+            grid[sticker.y as usize][sticker.x as usize] = Some(sticker.color);
+        }
+
+        grid
+    }
+
+    /// Returns every sticker of the cube along with the cell of a 12 by 9 grid
+    /// it's drawn in, laid out in the same cross shape as
+    /// [`Cube::net_grid`]. Stickers are given in the order of
+    /// [`Cube::facelets`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{cube::*, cubie::*};
+    /// let layout = Cube::solved().unfolded_layout();
+    ///
+    /// assert_eq!(
+    ///     layout[0],
+    ///     Sticker {
+    ///         face: FaceKind::Top,
+    ///         color: Color::White,
+    ///         x: 3,
+    ///         y: 0,
+    ///     }
+    /// );
+    /// ```
+    pub fn unfolded_layout(&self) -> Vec<Sticker> {
+        let facelets = self.facelets();
+
+        FACELET_FACES
+            .iter()
+            .zip(&NET_ORIGINS)
+            .flat_map(|(face, (row, column))| {
+                (0..9).map(move |n| (*face, row + n / 3, column + n % 3))
+            })
+            .zip(facelets.iter())
+            .map(|((face, y, x), color)| Sticker {
+                face,
+                color: *color,
+                x,
+                y,
+            })
+            .collect()
+    }
+}
+
+/// Returns the net of a cube with the given facelets, as described for
+/// [`Cube::to_ansi_string`].
+pub(crate) fn ansi_net(
+    facelets: &[Color; 54],
+    scheme: PaletteScheme,
+) -> String {
+    let mut net = String::new();
+
+    write_net(facelets, &mut net, &|c| {
+        let (r, g, b) = c.to_rgb_scheme(scheme);
+        let (tr, tg, tb) = c.text_color();
+
+        format!(
+            "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m{}\x1b[0m",
+            r,
+            g,
+            b,
+            tr,
+            tg,
+            tb,
+            c.letter()
+        )
+    })
+    .expect("Writing to a String can't fail");
+
+    net
+}
+
+/// Writes the net of a cube with the given facelets, drawing each sticker with
+/// a function of its color which must give a single visible character.
+fn write_net(
+    facelets: &[Color; 54],
+    f: &mut dyn fmt::Write,
+    sticker: &dyn Fn(Color) -> String,
+) -> fmt::Result {
+    let row = |face: usize, r: usize| {
+        facelets[face * 9 + r * 3..face * 9 + r * 3 + 3]
+            .iter()
+            .map(|c| sticker(*c))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    // Faces are indexed in facelet order: up, right, front, down, left,
+    // back
+    for r in 0..3 {
+        writeln!(f, "      {}", row(0, r))?;
+    }
+    for r in 0..3 {
+        writeln!(f, "{} {} {} {}", row(4, r), row(2, r), row(1, r), row(5, r))?;
+    }
+    for r in 0..3 {
+        writeln!(f, "      {}", row(3, r))?;
+    }
+
+    Ok(())
+}
+
+/// Formats the cube as the letters of its colors, in the order of
+/// [`Cube::facelets`].
+impl fmt::Debug for Cube {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters: String =
+            self.facelets().iter().map(|c| c.letter()).collect();
+
+        write!(f, "Cube({})", letters)
+    }
+}
+
+/// Compares the cube's [facelets](Cube::facelets) to an array of colors.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*};
+/// let mut cube = Cube::solved();
+/// let facelets = cube.facelets();
+///
+/// assert_eq!(cube, facelets);
+///
+/// cube.apply(Move::R);
+/// assert_ne!(cube, facelets);
+/// ```
+impl PartialEq<[Color; 54]> for Cube {
+    fn eq(&self, other: &[Color; 54]) -> bool {
+        self.facelets()[..] == other[..]
+    }
+}
+
+/// Consumes the cube, yielding each of its elements in the order of
+/// [`Cube::elements`], so its cubies can be moved out.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::cube::*;
+/// let corners = Cube::solved()
+///     .into_iter()
+///     .filter(|cubie| cubie.face_slice().len() == 3)
+///     .count();
+///
+/// assert_eq!(corners, 8);
+/// ```
+impl IntoIterator for Cube {
+    type Item = Box<dyn Cubie>;
+    type IntoIter = std::vec::IntoIter<Box<dyn Cubie>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let elements: Box<[Box<dyn Cubie>]> = Box::new(self.elements);
+
+        elements.into_vec().into_iter()
+    }
+}
+
+/// Returns the net of a cube, as formatted by its [`Display`] implementation,
+/// before a sequence of moves and after each of them.
+///
+/// [`Display`]: fmt::Display
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*};
+/// let cube = Cube::solved();
+/// let nets = render_sequence_nets(&cube, &[Move::U]);
+///
+/// assert_eq!(nets.len(), 2);
+/// assert!(nets[1].contains("G G G R R R B B B O O O"));
+/// ```
+pub fn render_sequence_nets(start: &Cube, moves: &[Move]) -> Vec<String> {
+    start
+        .trace_sequence(moves)
+        .iter()
+        .map(|cube| cube.to_string())
+        .collect()
+}
+
+/// Returns the single face move which turns one cube into another, or None if
+/// no single face move does.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*};
+/// let before = Cube::solved();
+/// let mut after = before.clone();
+/// after.apply(Move::R);
+///
+/// assert_eq!(infer_move(&before, &after), Some(Move::R));
+/// assert_eq!(infer_move(&before, &before), None);
+/// ```
+pub fn infer_move(before: &Cube, after: &Cube) -> Option<Move> {
+    let target = after.key();
+
+    Move::all_basic().find(|m| {
+        let mut cube = before.clone();
+        cube.apply(*m);
+
+        cube.key() == target
+    })
+}
+
+/// Returns the positions of the four edges around the center of a face.
+fn cross_positions(on: FaceKind) -> [(i8, i8, i8); 4] {
+    let (x, y, z) = on.normal();
+    let mut positions = [(0, 0, 0); 4];
+    let faces = FaceKind::all();
+    let sides = faces
+        .iter()
+        .map(|side| side.normal())
+        .filter(|(a, b, c)| a * x + b * y + c * z == 0);
+
+    for (pos, (a, b, c)) in positions.iter_mut().zip(sides) {
+        *pos = (x + a, y + b, z + c);
+    }
+
+    positions
+}
+
+/// The position of an edge along with the direction one of its stickers faces.
+type EdgeState = ((i8, i8, i8), (i8, i8, i8));
+
+/// Returns where an edge ends up after a move.
+fn turn_edge(m: Move, edge: EdgeState) -> EdgeState {
+    let (mut pos, mut normal) = edge;
+
+    if layer_contains(m.layer, pos) {
+        for _ in 0..m.direction.quarter_turns() {
+            pos = rotate_position(m.layer, pos);
+            normal = rotate_position(m.layer, normal);
+        }
+    }
+
+    (pos, normal)
+}
+
+/// Returns a unique index for an edge state, below 729.
+fn edge_key(edge: EdgeState) -> usize {
+    let index = |(x, y, z): (i8, i8, i8)| ((x + 1) * 9 + (y + 1) * 3 + z + 1);
+
+    (index(edge.0) as usize) * 27 + index(edge.1) as usize
+}
+
+/// Returns the number of face moves needed to bring an edge from each of its
+/// 24 states to a goal state, indexed by [`edge_key`].
+fn edge_distances(goal: EdgeState) -> [u8; 729] {
+    let mut distances = [u8::MAX; 729];
+    let mut queue = VecDeque::new();
+    distances[edge_key(goal)] = 0;
+    queue.push_back(goal);
+
+    while let Some(edge) = queue.pop_front() {
+        let distance = distances[edge_key(edge)] + 1;
+
+        for m in Move::all_basic() {
+            let next = turn_edge(m, edge);
+
+            if distances[edge_key(next)] == u8::MAX {
+                distances[edge_key(next)] = distance;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Searches every sequence of face moves of a given length for one which
+/// brings each cross edge to the state its distances are measured to, leaving
+/// it in `path` if found. Sequences are cut short once an edge is further from
+/// its goal than the moves left.
+fn search_cross(
+    edges: [EdgeState; 4],
+    depth: usize,
+    distances: &[[u8; 729]],
+    path: &mut Vec<Move>,
+) -> bool {
+    let furthest = edges
+        .iter()
+        .zip(distances)
+        .map(|(edge, distances)| distances[edge_key(*edge)] as usize)
+        .max()
+        .unwrap_or(0);
+
+    if furthest > depth {
+        return false;
+    } else if furthest == 0 {
+        return true;
+    }
+
+    for m in legal_moves_after(path.last().copied()) {
+        let mut next = edges;
+        for edge in next.iter_mut() {
+            *edge = turn_edge(m, *edge);
+        }
+
+        path.push(m);
+        if search_cross(next, depth - 1, distances, path) {
+            return true;
+        }
+        path.pop();
+    }
+
+    false
+}
+
+/// Returns every arrangement of the corners which can be reached using only
+/// half turns, as the home position of the corner at each corner position.
+fn half_turn_corner_permutations() -> Vec<Vec<usize>> {
+    let corners: Vec<usize> = (0..POSITIONS.len())
+        .filter(|i| element_faces(*i).len() == 3)
+        .collect();
+    let mut found = vec![corners.clone()];
+    let mut queue = VecDeque::new();
+    queue.push_back(corners.clone());
+
+    while let Some(homes) = queue.pop_front() {
+        for layer in FACE_LAYERS.iter() {
+            let mut next = homes.clone();
+
+            for (k, i) in corners.iter().enumerate() {
+                let pos = element_position(*i);
+
+                if layer_contains(*layer, pos) {
+                    let to = element_index(rotate_position(
+                        *layer,
+                        rotate_position(*layer, pos),
+                    ));
+                    let j = corners.iter().position(|c| *c == to).unwrap();
+
+                    next[j] = homes[k];
+                }
+            }
+
+            if !found.contains(&next) {
+                found.push(next.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    found
+}
+
+/// Returns true if a permutation, given as the values of its elements in
+/// order, is odd.
+fn permutation_parity(values: &[usize]) -> bool {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+
+    let mut visited = vec![false; values.len()];
+    let mut odd = false;
+
+    for start in 0..values.len() {
+        let mut i = start;
+
+        while !visited[i] {
+            visited[i] = true;
+            i = sorted.binary_search(&values[i]).unwrap();
+
+            if i != start {
+                odd = !odd;
+            }
+        }
+    }
+
+    odd
+}
+
+/// The colors of the faces of a solved cube.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, cubie::*};
+/// let scheme = ColorScheme::WESTERN;
+///
+/// assert_eq!(scheme.color(FaceKind::Front), Color::Green);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+    colors: [Color; 6],
+}
+
+impl ColorScheme {
+    /// The standard Western color scheme used by [`Cube::solved`], with
+    /// white opposite yellow, green opposite blue, and red opposite orange.
+    pub const WESTERN: Self = Self::new([
+        solved_color(FaceKind::Top),
+        solved_color(FaceKind::Left),
+        solved_color(FaceKind::Right),
+        solved_color(FaceKind::Front),
+        solved_color(FaceKind::Back),
+        solved_color(FaceKind::Bottom),
+    ]);
+
+    /// The Japanese color scheme, with white opposite blue, green opposite
+    /// yellow, and red opposite orange.
+    pub const JAPANESE: Self = Self::new([
+        Color::White,
+        Color::Orange,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+    ]);
+
+    /// Creates a scheme from the color of each face, in the order of
+    /// [`FaceKind::all`].
+    pub const fn new(colors: [Color; 6]) -> Self {
+        Self {
+            colors,
+        }
+    }
+
+    /// Returns the color of a face in the scheme.
+    pub fn color(&self, face: FaceKind) -> Color {
+        match FaceKind::all().iter().position(|f| *f == face) {
+            Some(i) => self.colors[i],
+            None => Color::Uninit,
+        }
+    }
+}
+
+/// A saved state of a [`Cube`], taken with [`Cube::snapshot`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CubeSnapshot {
+    facelets: [Color; 54],
+}
+
+/// A sticker of a [`Cube`] placed on a flat grid, as returned by
+/// [`Cube::unfolded_layout`]. `x` counts columns from the left and `y` counts
+/// rows from the top.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sticker {
+    pub face: FaceKind,
+    pub color: Color,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// A record of a sequence of moves applied to a [`Cube`], made by
+/// [`Cube::apply_sequence_logged`]. States are stored as [keys](Cube::key),
+/// so the cube can be rebuilt from the record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Replay {
+    /// The key of the cube before the moves.
+    pub initial_key: [u8; 27],
+    /// The moves applied to the cube.
+    pub moves: Vec<Move>,
+    /// The key of the cube after the moves.
+    pub final_key: [u8; 27],
+}
+
+impl Replay {
+    /// Returns true if applying the moves to the initial state gives the
+    /// final state, and false if the record is inconsistent, such as after
+    /// being tampered with.
+    pub fn verify(&self) -> bool {
+        match Cube::from_reader(&self.initial_key[..]) {
+            Ok(mut cube) => {
+                cube.apply_sequence(&self.moves).key() == self.final_key
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// A summary of a sequence of moves applied to a [`Cube`], made by
+/// [`Cube::apply_and_report`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ApplyReport {
+    /// The number of moves in the sequence.
+    pub applied: usize,
+    /// The number of moves removed by merging them with their neighbors or
+    /// cancelling them out, as done by [`optimize`].
+    pub cancelled: usize,
+    /// Whether the cube was solved after the moves.
+    pub solved: bool,
+}
+
+/// The nested groups of cube states used by Thistlethwaite's algorithm, which
+/// solves a cube by moving it into each group in turn using only the moves of
+/// the group before.
+///
+/// Groups are ordered from the largest to the smallest, so a cube in a group
+/// is also in every group before it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subgroup {
+    /// Every state of the cube, reached with any moves.
+    G0,
+    /// States with oriented edges, reached with `<U, D, L, R, F2, B2>`.
+    G1,
+    /// States which also have oriented corners and the middle layer edges
+    /// in the middle layer, reached with `<U, D, L2, R2, F2, B2>`.
+    G2,
+    /// States reached with only half turns, `<U2, D2, L2, R2, F2, B2>`.
+    G3,
+    /// The solved state alone.
+    Solved,
+}
+
+/// An error returned when an operation can't be carried out on a [`Cube`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CubeError {
+    /// No face of the cube has a center of the color.
+    MissingCenter(Color),
+    /// A sticker has no color.
+    UninitializedSticker,
+    /// The centers of opposite faces don't have opposite colors.
+    InconsistentCenters,
+    /// The element at the index doesn't have the colors of any piece.
+    InvalidPiece(usize),
+    /// The element at the index is a piece which appears more than once.
+    DuplicatePiece(usize),
+    /// The corners are twisted in a way turns can't reach.
+    TwistedCorner,
+    /// The edges are flipped in a way turns can't reach.
+    FlippedEdge,
+    /// An odd number of pairs of pieces are swapped.
+    PermutationParity,
+    /// A sequence of the given length is longer than allowed.
+    SequenceTooLong(usize),
+    /// The layer index is deeper than the cube.
+    InvalidLayer(usize),
+    /// A turn was given a number of quarter turns other than 1 to 3.
+    InvalidQuarterTurns(u8),
+    /// A piece was given an orientation other than 0 to 2 for a corner or 0
+    /// to 1 for an edge.
+    InvalidOrientation(u8),
+}
+
+impl fmt::Display for CubeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CubeError::MissingCenter(color) => {
+                write!(f, "no face has a {:?} center", color)
+            }
+            CubeError::UninitializedSticker => {
+                write!(f, "sticker has no color")
+            }
+            CubeError::InconsistentCenters => {
+                write!(f, "opposite centers aren't opposite colors")
+            }
+            CubeError::InvalidPiece(i) => {
+                write!(f, "element {} isn't a piece", i)
+            }
+            CubeError::DuplicatePiece(i) => {
+                write!(f, "element {} is a duplicate piece", i)
+            }
+            CubeError::TwistedCorner => write!(f, "a corner is twisted"),
+            CubeError::FlippedEdge => write!(f, "an edge is flipped"),
+            CubeError::PermutationParity => write!(f, "two pieces are swapped"),
+            CubeError::SequenceTooLong(len) => {
+                write!(f, "sequence of {} moves is too long", len)
+            }
+            CubeError::InvalidLayer(layer) => {
+                write!(f, "layer {} is out of range", layer)
+            }
+            CubeError::InvalidQuarterTurns(turns) => {
+                write!(f, "{} isn't 1 to 3 quarter turns", turns)
+            }
+            CubeError::InvalidOrientation(orientation) => {
+                write!(f, "{} isn't a valid piece orientation", orientation)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CubeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::algorithms::alg;
+    use crate::cube::cubie::{Corner as CornerCubie, Edge as EdgeCubie};
+    use crate::cube::moves::Sequence;
+    use crate::cube::scramble::assert_not_redundant;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn cube_init() {
+        let c = Cube::new();
+
+        assert_eq!(c.elements.len(), 26);
+    }
+
+    #[test]
+    fn get_face_array() {
+        let c = Cube::new();
+
+        let cf: Face = initialize_cube_face!(c, FaceKind::Top);
+
+        assert_eq!(cf.elements.len(), 9);
+        let cubie: &Box<dyn Cubie> = &c.elements[0];
+        let cornercubie: &CornerCubie =
+            match cubie.as_any().downcast_ref::<CornerCubie>() {
+                Some(i) => i,
+                None => panic!("cubie isn't a CornerCubie!!"),
+            };
+
+        let cubie2: &Box<dyn Cubie> = cf.elements[0];
+        let cornercubie2: &CornerCubie =
+            match cubie2.as_any().downcast_ref::<CornerCubie>() {
+                Some(i) => i,
+                None => panic!("cubie2 isn't a CornerCubie!!"),
+            };
+
+        assert_eq!(cornercubie.faces, cornercubie2.faces);
+    }
+
+    #[test]
+    fn positions_table() {
+        for (i, pos) in POSITIONS.iter().enumerate() {
+            assert_ne!(*pos, (0, 0, 0));
+            assert!(POSITIONS[i + 1..].iter().all(|other| other != pos));
+        }
+    }
+
+    #[test]
+    fn raw_accessors() {
+        let c = Cube::solved();
+        let corner = |cubie: &Box<dyn Cubie>| cubie.face_slice().len() == 3;
+
+        assert!(c.corners().iter().all(|cubie| corner(cubie)));
+
+        for (i, row) in c.rows().iter().enumerate() {
+            assert_eq!(row.center.is_none(), i == 4);
+            assert_eq!(corner(row.left), i / 3 != 1 && i % 3 != 1);
+        }
+
+        for (i, column) in c.columns().iter().enumerate() {
+            assert_eq!(column.center.is_none(), i == 4);
+            assert_eq!(corner(column.top), i % 2 == 0 && i != 4);
+        }
+
+        // Every column ends in the bottom layer
+        for column in c.columns().iter() {
+            assert_eq!(column.bottom.face_slice()[0].color, Color::Yellow);
+        }
+    }
+
+    #[test]
+    fn element_positions() {
+        for i in 0..26 {
+            let c = Cube::new();
+
+            assert_eq!(element_index(element_position(i)), i);
+            assert_eq!(
+                element_faces(i).len(),
+                c.elements[i].face_slice().len()
+            );
+        }
+    }
+
+    #[test]
+    fn move_order() {
+        let mut c = Cube::solved();
+
+        for m in Move::parse_sequence("U D L R F B M E S x y z").unwrap() {
+            for _ in 0..4 {
+                c.apply(m);
+            }
+            assert!(c.is_solved());
+        }
+
+        assert!(c.is_solved());
+    }
+
+    #[test]
+    fn move_colors() {
+        let mut c = Cube::solved();
+        c.apply(Move::R);
+
+        // The right column of the front face moves up to the top face
+        assert_eq!(c.face_colors(FaceKind::Top)[8], Color::Green);
+        assert_eq!(c.face_colors(FaceKind::Front)[8], Color::Yellow);
+        assert_eq!(c.face_colors(FaceKind::Right), [Color::Red; 9]);
+
+        c.apply_sequence(&Move::parse_sequence("R' U F'").unwrap());
+        c.apply_sequence(&Move::parse_sequence("F U'").unwrap());
+        assert!(c.is_solved());
+    }
+
+    #[test]
+    fn wide_moves() {
+        let equivalents = [
+            ("Rw", "R M'"),
+            ("Lw", "L M"),
+            ("Uw", "U E'"),
+            ("Dw", "D E"),
+            ("Fw", "F S"),
+            ("Bw", "B S'"),
+            ("x", "Rw L'"),
+            ("r2 u'", "R2 M2 U' E"),
+        ];
+
+        for (wide, turns) in equivalents.iter() {
+            let mut a = Cube::solved();
+            a.apply_sequence(&Move::parse_sequence(wide).unwrap());
+
+            let mut b = Cube::solved();
+            b.apply_sequence(&Move::parse_sequence(turns).unwrap());
+
+            assert_eq!(a.facelets(), b.facelets(), "{}", wide);
+        }
+
+        let scramble = "D2 R' Fw2 y U2 L' r F' x2 B' Uw R2 z' E S' M2";
+        let moves = Move::parse_sequence(scramble).unwrap();
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&moves);
+        assert!(!cube.is_solved());
+
+        cube.apply_sequence(&Sequence(moves).inverse().0);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn orientation() {
+        let solved = Cube::solved();
+        assert!(solved.edges_oriented());
+        assert!(solved.corners_oriented());
+
+        let mut cube = Cube::solved();
+        cube.apply(Move::F);
+        assert!(!cube.edges_oriented());
+        assert!(!cube.corners_oriented());
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U2 L' D F2 B2").unwrap());
+        assert!(cube.edges_oriented());
+        assert!(!cube.corners_oriented());
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("U D' R2 L2 F2 B2").unwrap());
+        assert!(cube.edges_oriented());
+        assert!(cube.corners_oriented());
+    }
+
+    #[test]
+    fn net_display() {
+        let expected = "      W W W\n".repeat(3)
+            + &"O O O G G G R R R B B B\n".repeat(3)
+            + &"      Y Y Y\n".repeat(3);
+
+        assert_eq!(Cube::solved().to_string(), expected);
+    }
+
+    #[test]
+    fn sequence_nets() {
+        let start = Cube::solved();
+        let moves = Move::parse_sequence("R U R' U'").unwrap();
+        let nets = render_sequence_nets(&start, &moves);
+
+        assert_eq!(nets.len(), moves.len() + 1);
+        assert_eq!(nets[0], start.to_string());
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&moves);
+        assert_eq!(nets[4], cube.to_string());
+    }
+
+    #[test]
+    fn horizontal_layers() {
+        let cube = Cube::solved();
+        let top = cube.layer(1);
+
+        // The first four corners are the top corners
+        for pos in 0..4 {
+            let corner = cube.corner_raw(pos);
+
+            assert!(top.iter().any(|e| std::ptr::eq(e.unwrap(), corner)));
+        }
+
+        let middle = cube.layer(0);
+        assert_eq!(middle.iter().filter(|e| e.is_none()).count(), 1);
+        assert!(middle[4].is_none());
+
+        let bottom = cube.layer(-1);
+        assert!(bottom.iter().all(|e| e.is_some()));
+        assert!(std::ptr::eq(
+            bottom[4].unwrap(),
+            &cube.elements[element_index((0, -1, 0))]
+        ));
+    }
+
+    #[test]
+    fn centers_consistent() {
+        let mut cube = Cube::solved();
+        assert!(cube.centers_consistent());
+
+        let top = element_index(FaceKind::Top.normal());
+        cube.elements[top].face_slice_mut()[0].color = Color::Red;
+        assert!(!cube.centers_consistent());
+    }
+
+    #[test]
+    fn all_face_kinds() {
+        let faces = FaceKind::all();
+
+        for face in faces.iter() {
+            assert_eq!(faces.iter().filter(|f| *f == face).count(), 1);
+        }
+        assert_eq!(faces.len(), 6);
+    }
+
+    #[test]
+    fn turn_color_face() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("x z' R U").unwrap());
+
+        let white = *FaceKind::all()
+            .iter()
+            .find(|f| cube.face_colors(**f)[4] == Color::White)
+            .unwrap();
+
+        let mut expected = cube.clone();
+        expected.apply(Move::new(white.layer(), Direction::Double));
+
+        cube.turn_color_face(Color::White, Direction::Double)
+            .unwrap();
+        assert_eq!(cube.facelets(), expected.facelets());
+
+        assert_eq!(
+            cube.turn_color_face(Color::Uninit, Direction::Clockwise),
+            Err(CubeError::MissingCenter(Color::Uninit))
+        );
+    }
+
+    #[test]
+    fn patterns() {
+        for cube in
+            &[Cube::checkerboard(), Cube::cube_in_cube(), Cube::six_spot()]
+        {
+            assert!(!cube.is_solved());
+            assert_eq!(cube.validate(), Ok(()));
+        }
+
+        let checkerboard = Cube::checkerboard();
+        for face in FaceKind::all().iter() {
+            let colors = checkerboard.face_colors(*face);
+
+            for (i, color) in colors.iter().enumerate() {
+                assert_eq!(*color == colors[4], i % 2 == 0);
+            }
+        }
+
+        let six_spot = Cube::six_spot();
+        for face in FaceKind::all().iter() {
+            let colors = six_spot.face_colors(*face);
+
+            assert!(colors
+                .iter()
+                .all(|c| (*c == colors[0]) != (*c == colors[4])));
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut rng = StdRng::seed_from_u64(647);
+        for _ in 0..10 {
+            let mut cube = Cube::solved();
+            cube.scramble(30, &mut rng);
+            cube.apply_sequence(&Move::parse_sequence("M E' S2 x y'").unwrap());
+
+            assert_eq!(cube.validate(), Ok(()));
+        }
+
+        let corner = element_index((1, 1, 1));
+        let edge = element_index((0, 1, 1));
+
+        let mut cube = Cube::solved();
+        cube.elements[corner].face_slice_mut().rotate_left(1);
+        assert_eq!(cube.validate(), Err(CubeError::TwistedCorner));
+
+        let mut cube = Cube::solved();
+        cube.elements[edge].face_slice_mut().swap(0, 1);
+        assert_eq!(cube.validate(), Err(CubeError::FlippedEdge));
+
+        let mut cube = Cube::solved();
+        let other = element_index((1, 1, 0));
+        cube.elements.swap(edge, other);
+        cube.elements[edge].face_slice_mut()[0].color = Color::White;
+        cube.elements[edge].face_slice_mut()[1].color = Color::Green;
+        cube.elements[other].face_slice_mut()[0].color = Color::White;
+        cube.elements[other].face_slice_mut()[1].color = Color::Red;
+        assert_eq!(cube.validate(), Ok(()));
+        cube.elements[edge].face_slice_mut()[1].color = Color::Red;
+        cube.elements[other].face_slice_mut()[1].color = Color::Green;
+        assert_eq!(cube.validate(), Err(CubeError::PermutationParity));
+
+        let mut cube = Cube::solved();
+        cube.elements[edge].face_slice_mut()[1].color = Color::Yellow;
+        assert_eq!(cube.validate(), Err(CubeError::InvalidPiece(edge)));
+
+        let mut cube = Cube::solved();
+        cube.elements[edge].face_slice_mut()[1].color = Color::Blue;
+        assert_eq!(cube.validate(), Err(CubeError::DuplicatePiece(edge)));
+
+        let mut cube = Cube::solved();
+        cube.elements[edge].face_slice_mut()[1].color = Color::Uninit;
+        assert_eq!(cube.validate(), Err(CubeError::UninitializedSticker));
+    }
+
+    #[test]
+    fn is_cross_solved() {
+        let solved = Cube::solved();
+        for on in FaceKind::all().iter() {
+            assert!(solved.is_cross_solved(*on));
+        }
+
+        // R moves an edge of every cross except the left one
+        let mut cube = Cube::solved();
+        cube.apply(Move::R);
+        for on in FaceKind::all().iter() {
+            assert_eq!(cube.is_cross_solved(*on), *on == FaceKind::Left);
+        }
+
+        // Flipping an edge in place unsolves both crosses it belongs to
+        let mut cube = Cube::solved();
+        let edge = element_index((0, 1, 1));
+        cube.elements[edge].face_slice_mut().swap(0, 1);
+        assert!(!cube.is_cross_solved(FaceKind::Top));
+        assert!(!cube.is_cross_solved(FaceKind::Front));
+        assert!(cube.is_cross_solved(FaceKind::Bottom));
+    }
+
+    #[test]
+    fn solve_cross() {
+        let mut rng = StdRng::seed_from_u64(649);
+
+        for on in FaceKind::all().iter() {
+            let mut cube = Cube::solved();
+            cube.scramble(25, &mut rng);
+
+            let cross = cube.solve_cross(*on);
+            assert!(cross.len() <= 8);
+
+            cube.apply_sequence(&cross);
+            assert!(cube.is_cross_solved(*on));
+        }
+
+        assert!(Cube::solved().solve_cross(FaceKind::Top).is_empty());
+    }
+
+    #[test]
+    fn rotate_to_put_color_on() {
+        let colors = [
+            Color::White,
+            Color::Yellow,
+            Color::Red,
+            Color::Orange,
+            Color::Green,
+            Color::Blue,
+        ];
+
+        for face in FaceKind::all().iter() {
+            for color in colors.iter() {
+                let mut cube = Cube::solved();
+                cube.apply_sequence(&Move::parse_sequence("R U x y'").unwrap());
+                let before = cube.clone();
+
+                let rotations =
+                    cube.rotate_to_put_color_on(*face, *color).unwrap();
+
+                assert!(rotations.len() <= 1);
+                assert!(rotations.iter().all(|m| m.layer.is_rotation()));
+                assert_eq!(cube.face_colors(*face)[4], *color);
+                assert_eq!(cube.validate(), Ok(()));
+                assert_eq!(
+                    before.clone().apply_sequence(&rotations).facelets(),
+                    cube.facelets()
+                );
+            }
+        }
+
+        let mut cube = Cube::solved();
+        assert_eq!(
+            cube.rotate_to_put_color_on(FaceKind::Top, Color::Uninit),
+            Err(CubeError::MissingCenter(Color::Uninit))
+        );
+    }
+
+    #[test]
+    fn subgroups() {
+        let in_group = |moves: &str| {
+            let mut cube = Cube::solved();
+            cube.apply_sequence(&Move::parse_sequence(moves).unwrap());
+
+            cube.subgroup()
+        };
+
+        assert_eq!(Cube::solved().subgroup(), Subgroup::Solved);
+        assert_eq!(in_group("R U F L"), Subgroup::G0);
+        assert_eq!(in_group("R U R' U' L D2 F2 L'"), Subgroup::G1);
+        assert_eq!(in_group("U R2 D' F2 L2 U B2"), Subgroup::G2);
+        assert_eq!(in_group("U2 R2 F2 D2 L2 B2 R2"), Subgroup::G3);
+
+        // Quarter turns of U and D keep every edge in its slice, but move
+        // the corners to places half turns can't reach
+        assert_eq!(in_group("U D'"), Subgroup::G2);
+        assert_eq!(half_turn_corner_permutations().len(), 96);
+
+        assert!(Subgroup::G1 < Subgroup::G2);
+        assert!(in_group("R U R' U'") >= Subgroup::G1);
+        assert!(in_group("R U R' U'") < Subgroup::G2);
+    }
+
+    #[test]
+    fn apply_n() {
+        let mut cube = Cube::solved();
+        cube.apply_n(Move::R, 4);
+        assert!(cube.is_solved());
+
+        cube.apply_n(Move::U, 2);
+        assert_eq!(
+            cube.facelets(),
+            Cube::from_moves_iter(vec![Move::U2]).facelets()
+        );
+
+        cube.apply_n(Move::F, 0).apply_n(Move::U2, 1);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut rng = StdRng::seed_from_u64(656);
+        let mut cube = Cube::solved();
+        cube.scramble(10, &mut rng);
+
+        let snapshot = cube.snapshot();
+        let expected = cube.facelets();
+
+        cube.scramble(20, &mut rng);
+        assert_ne!(cube.facelets(), expected);
+
+        cube.restore(&snapshot);
+        assert_eq!(cube.facelets(), expected);
+        assert_eq!(cube.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn edges() {
+        let cube = Cube::solved();
+        let edges = cube.edges();
+
+        for (i, edge) in edges.iter().enumerate() {
+            assert_eq!(edge.face_slice().len(), 2);
+            assert!(edge.as_any().downcast_ref::<EdgeCubie>().is_some());
+
+            for other in edges[..i].iter() {
+                assert!(!std::ptr::eq(*edge, *other));
+                assert_ne!(edge.face_slice(), other.face_slice());
+            }
+        }
+
+        let edge = cube.edge(EdgePosition::MiddleFrontRight);
+        let colors: Vec<Color> =
+            edge.face_slice().iter().map(|f| f.color).collect();
+        assert_eq!(colors, vec![Color::Green, Color::Red]);
+    }
+
+    #[test]
+    fn cfop_stages() {
+        let mut cube = Cube::solved();
+        assert!(cube.f2l_solved() && cube.oll_solved() && cube.pll_solved());
+
+        cube.apply_sequence(&alg("sune").unwrap());
+        assert!(cube.f2l_solved());
+        assert!(!cube.oll_solved());
+        assert!(!cube.pll_solved());
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&alg("t-perm").unwrap());
+        assert!(cube.f2l_solved());
+        assert!(cube.oll_solved());
+        assert!(!cube.pll_solved());
+        assert!(!cube.is_solved());
+
+        cube.apply_sequence(&alg("sexy-move").unwrap());
+        assert!(!cube.f2l_solved());
+        assert!(!cube.oll_solved());
+    }
+
+    #[test]
+    fn bounded_sequences() {
+        let mut cube = Cube::solved();
+        assert_eq!(cube.apply_sequence_bounded(&[], 0), Ok(()));
+        assert!(cube.is_solved());
+
+        let moves = vec![Move::R; 1000];
+        assert_eq!(
+            cube.apply_sequence_bounded(&moves, 999),
+            Err(CubeError::SequenceTooLong(1000))
+        );
+        assert!(cube.is_solved());
+
+        assert_eq!(cube.apply_sequence_bounded(&moves[..3], 3), Ok(()));
+        assert_eq!(
+            cube.facelets(),
+            Cube::solved().apply(Move::R_PRIME).facelets()
+        );
+    }
+
+    #[test]
+    fn scramble_to_depth() {
+        let mut rng = StdRng::seed_from_u64(662);
+
+        for depth in 0..=4 {
+            let mut cube = Cube::solved();
+            let scramble = cube.scramble_to_depth(depth, &mut rng);
+            assert_eq!(scramble.len(), depth as usize);
+
+            let solution =
+                OptimalSolver::new(depth as usize).solve(&cube).unwrap();
+            assert_eq!(solution.len(), depth as usize);
+        }
+    }
+
+    #[test]
+    fn reader_writer_round_trip() {
+        let mut rng = StdRng::seed_from_u64(664);
+        let mut cubes = vec![Cube::solved(), Cube::checkerboard()];
+
+        for _ in 0..3 {
+            let mut cube = Cube::solved();
+            cube.scramble(20, &mut rng);
+            cubes.push(cube);
+        }
+
+        let mut buffer = Vec::new();
+        for cube in &cubes {
+            cube.to_writer(&mut buffer).unwrap();
+        }
+
+        let mut reader = &buffer[..];
+        for cube in &cubes {
+            let read = Cube::from_reader(&mut reader).unwrap();
+            assert_eq!(read.facelets(), cube.facelets());
+        }
+
+        let error = Cube::from_reader(&mut reader).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let error = Cube::from_reader(&[255; 27][..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn checked_turns() {
+        let mut cube = Cube::solved();
+        assert_eq!(
+            cube.turn_checked(FaceKind::Front, 3, 1),
+            Err(CubeError::InvalidLayer(3))
+        );
+        assert_eq!(
+            cube.turn_checked(FaceKind::Front, 0, 0),
+            Err(CubeError::InvalidQuarterTurns(0))
+        );
+        assert_eq!(
+            cube.turn_checked(FaceKind::Front, 0, 4),
+            Err(CubeError::InvalidQuarterTurns(4))
+        );
+        assert!(cube.is_solved());
+
+        let turns = [
+            (FaceKind::Top, 0, 1, "U"),
+            (FaceKind::Top, 1, 1, "E'"),
+            (FaceKind::Bottom, 1, 2, "E2"),
+            (FaceKind::Right, 1, 3, "M"),
+            (FaceKind::Back, 1, 1, "S'"),
+            (FaceKind::Left, 2, 3, "R"),
+        ];
+        for (face, layer, quarter_turns, expected) in turns.iter() {
+            let mut cube = Cube::solved();
+            cube.turn_checked(*face, *layer, *quarter_turns).unwrap();
+
+            let moves = Move::parse_sequence(expected).unwrap();
+            let expected = Cube::from_moves_iter(moves);
+            assert_eq!(cube.facelets(), expected.facelets());
+        }
+    }
+
+    #[test]
+    fn ansi_net() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F'").unwrap());
+
+        let ansi = cube.to_ansi_string(PaletteScheme::ColorBlindSafe);
+        assert!(ansi.contains("\x1b[48;2;170;30;80m\x1b[38;2;255;255;255mR"));
+
+        let mut plain = String::new();
+        let mut escape = false;
+        for c in ansi.chars() {
+            match c {
+                '\x1b' => escape = true,
+                'm' if escape => escape = false,
+                _ if !escape => plain.push(c),
+                _ => {}
+            }
+        }
+        assert_eq!(plain, cube.to_string());
+    }
+
+    #[test]
+    fn orientation_vectors() {
+        let mut cube = Cube::solved();
+        assert_eq!(cube.corner_orientations(), [0; 8]);
+        assert_eq!(cube.edge_orientations(), [0; 12]);
+
+        cube.apply(Move::F);
+        assert_eq!(cube.edge_orientations(), [
+            0, 0, 0, 1, 0, 0, 1, 1, 0, 0, 0, 1
+        ]);
+        assert_eq!(cube.corner_orientations(), [0, 0, 1, 2, 0, 0, 2, 1]);
+
+        cube.apply_sequence(&Move::parse_sequence("y2 z2").unwrap());
+        assert_eq!(cube.edge_orientations().iter().sum::<u8>(), 4);
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F' x z'").unwrap());
+        cube.apply_sequence(&Move::parse_sequence("z x' F U' R'").unwrap());
+        assert_eq!(cube.corner_orientations(), [0; 8]);
+        assert_eq!(cube.edge_orientations(), [0; 12]);
+    }
+
+    #[test]
+    fn scramble_biased() {
+        let mut rng = StdRng::seed_from_u64(669);
+        let weights = MoveWeights::uniform()
+            .with_weight(Layer::B, 0)
+            .with_weight(Layer::U, 5);
+
+        let mut cube = Cube::solved();
+        let scramble = cube.scramble_biased(200, &weights, &mut rng);
+        assert_eq!(scramble.len(), 200);
+
+        assert!(scramble.iter().all(|m| m.layer != Layer::B));
+        assert_not_redundant(&scramble);
+
+        let turned =
+            |layer| scramble.iter().filter(|m| m.layer == layer).count();
+        assert!(turned(Layer::U) > turned(Layer::D));
+
+        let mut expected = Cube::solved();
+        expected.apply_sequence(&scramble);
+        assert_eq!(cube.facelets(), expected.facelets());
+    }
+
+    #[test]
+    fn rotate_color_face_cw() {
+        let mut scrambled = Cube::solved();
+        scrambled.apply_sequence(&Move::parse_sequence("R U' x F2 z").unwrap());
+
+        for face in FaceKind::all().iter() {
+            let color = scrambled.color_of_face(*face);
+
+            let mut by_color = scrambled.clone();
+            by_color.rotate_color_face_cw(color).unwrap();
+
+            let mut by_face = scrambled.clone();
+            by_face.rotate_face_cw(*face);
+
+            assert_eq!(by_color.facelets(), by_face.facelets());
+        }
+
+        let mut cube = Cube::new();
+        assert_eq!(
+            cube.rotate_color_face_cw(Color::Red),
+            Err(CubeError::MissingCenter(Color::Red))
+        );
+    }
+
+    #[test]
+    fn net_grid() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F'").unwrap());
+
+        let grid = cube.net_grid();
+        let stickers = grid.iter().flatten().filter(|c| c.is_some()).count();
+        assert_eq!(stickers, 54);
+
+        let rows: Vec<String> = grid
+            .iter()
+            .map(|row| {
+                let row: Vec<String> = row
+                    .iter()
+                    .map(|c| c.map_or(' ', |c| c.letter()).to_string())
+                    .collect();
+
+                row.join(" ").trim_end().to_string()
+            })
+            .collect();
+        let net: Vec<String> =
+            cube.to_string().lines().map(|l| l.to_string()).collect();
+        assert_eq!(rows, net);
+    }
+
+    #[test]
+    fn apply_with_noise() {
+        let mut rng = StdRng::seed_from_u64(672);
+        let moves = Move::parse_sequence("R U R' U' M2 x F2").unwrap();
+
+        let mut noisy = Cube::solved();
+        let applied = noisy.apply_with_noise(&moves, 0.0, &mut rng);
+        assert_eq!(applied, moves);
+
+        let mut expected = Cube::solved();
+        expected.apply_sequence(&moves);
+        assert_eq!(noisy.facelets(), expected.facelets());
+
+        let mut noisy = Cube::solved();
+        let applied = noisy.apply_with_noise(&moves, 1.0, &mut rng);
+        for (a, m) in applied.iter().zip(&moves) {
+            assert_ne!(a, m);
+        }
+
+        let mut expected = Cube::solved();
+        expected.apply_sequence(&applied);
+        assert_eq!(noisy.facelets(), expected.facelets());
+    }
+
+    #[test]
+    fn solved_key() {
+        assert_eq!(Cube::solved().key(), SOLVED_KEY);
+
+        let mut rng = StdRng::seed_from_u64(673);
+        let mut cube = Cube::solved();
+
+        for _ in 0..2000 {
+            let m = cube.apply_random_move(&mut rng);
+            assert_eq!(cube.key() == SOLVED_KEY, cube.is_solved(), "{}", m);
+
+            if rng.gen_bool(0.3) {
+                cube.apply(m.inverse());
+                assert_eq!(cube.key() == SOLVED_KEY, cube.is_solved());
+            }
+        }
+
+        let mut rotated = Cube::solved();
+        rotated.apply(Move::Y);
+        assert!(rotated.is_solved());
+        assert_ne!(rotated.key(), SOLVED_KEY);
+    }
+
+    #[test]
+    fn key_matches_facelets() {
+        let mut rng = StdRng::seed_from_u64(673);
+
+        for _ in 0..20 {
+            let mut cube = Cube::solved();
+            cube.scramble(20, &mut rng);
+            cube.apply_sequence(&Move::parse_sequence("M E' x y2").unwrap());
+
+            let mut expected = [0; 27];
+            for (byte, pair) in
+                expected.iter_mut().zip(cube.facelets().chunks(2))
+            {
+                *byte = (pair[0] as u8) << 4 | pair[1] as u8;
+            }
+            assert_eq!(cube.key(), expected);
+        }
+    }
+
+    #[test]
+    fn solved_checks_agree() {
+        let scan = |cube: &Cube| {
+            FaceKind::all().iter().all(|face| {
+                let colors = cube.face_colors(*face);
+
+                colors.iter().all(|c| *c == colors[0])
+            })
+        };
+
+        let mut rng = StdRng::seed_from_u64(673);
+        let mut cube = Cube::solved();
+
+        for _ in 0..500 {
+            let m = cube.apply_random_move(&mut rng);
+            assert_eq!(cube.is_solved(), scan(&cube));
+
+            if rng.gen_bool(0.5) {
+                cube.apply(m.inverse());
+                assert_eq!(cube.is_solved(), scan(&cube));
+            }
+        }
+
+        for moves in &["x", "y' z2", "M E S", "R L' x"] {
+            let mut cube = Cube::solved();
+            cube.apply_sequence(&Move::parse_sequence(moves).unwrap());
+
+            assert_eq!(cube.is_solved(), scan(&cube), "{}", moves);
+        }
+
+        let recolored = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+        assert!(recolored.is_solved());
+    }
+
+    #[test]
+    fn infer_move() {
+        let mut before = Cube::solved();
+        before.apply_sequence(&Move::parse_sequence("F2 D' L").unwrap());
+
+        for m in Move::all_basic() {
+            let mut after = before.clone();
+            after.apply(m);
+
+            assert_eq!(super::infer_move(&before, &after), Some(m));
+        }
+
+        let mut after = before.clone();
+        after.apply_sequence(&Move::parse_sequence("R U").unwrap());
+        assert_eq!(super::infer_move(&before, &after), None);
+
+        let mut after = before.clone();
+        after.apply(Move::M);
+        assert_eq!(super::infer_move(&before, &after), None);
+    }
+
+    #[test]
+    fn distance_to() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("L2 B' U").unwrap());
+        assert_eq!(cube.distance_to(&cube, 0), Some(0));
+
+        for (moves, distance) in
+            &[("R U", 2), ("R L", 2), ("R R", 1), ("F R U' D2", 4)]
+        {
+            let mut other = cube.clone();
+            other.apply_sequence(&Move::parse_sequence(moves).unwrap());
+
+            assert_eq!(cube.distance_to(&other, 5), Some(*distance));
+            assert_eq!(other.distance_to(&cube, 5), Some(*distance));
+            assert_eq!(cube.distance_to(&other, distance - 1), None);
+        }
+    }
+
+    #[test]
+    fn replays() {
+        let mut rng = StdRng::seed_from_u64(679);
+        let mut cube = Cube::solved();
+        cube.scramble(15, &mut rng);
+
+        let moves = Move::parse_sequence("R U2 x M' F").unwrap();
+        let replay = cube.clone().apply_sequence_logged(&moves);
+        assert_eq!(replay.initial_key, cube.key());
+        assert_eq!(replay.moves, moves);
+        assert!(replay.verify());
+
+        let mut tampered = replay.clone();
+        tampered.final_key = SOLVED_KEY;
+        assert!(!tampered.verify());
+
+        let mut tampered = replay.clone();
+        tampered.moves.pop();
+        assert!(!tampered.verify());
+
+        let mut tampered = replay;
+        tampered.initial_key = [255; 27];
+        assert!(!tampered.verify());
+    }
+
+    #[test]
+    fn with_scheme() {
+        let misplaced = |cube: &Cube| {
+            FaceKind::all()
+                .iter()
+                .map(|face| {
+                    let colors = cube.face_colors(*face);
+
+                    colors.iter().filter(|c| **c != colors[4]).count()
+                })
+                .sum::<usize>()
+        };
+
+        let japanese = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+        assert!(japanese.is_solved());
+        for face in FaceKind::all().iter() {
+            assert_eq!(
+                japanese.face_colors(*face),
+                [ColorScheme::JAPANESE.color(*face); 9]
+            );
+        }
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U' F2 L D B' y").unwrap());
+        let recolored = cube.with_scheme(&ColorScheme::JAPANESE);
+        assert_eq!(misplaced(&recolored), misplaced(&cube));
+        assert_ne!(recolored.facelets(), cube.facelets());
+
+        let western = recolored.with_scheme(&ColorScheme::WESTERN);
+        assert_eq!(misplaced(&western), misplaced(&cube));
+    }
+
+    #[test]
+    fn face_elements() {
+        assert_eq!(super::face_elements(FaceKind::Left), [
+            0, 3, 6, 9, 12, 14, 17, 20, 23
+        ]);
+        assert_eq!(super::face_elements(FaceKind::Bottom), [
+            17, 18, 19, 20, 21, 22, 23, 24, 25
+        ]);
+    }
+
+    #[test]
+    fn face_sticker_order() {
+        for face in FaceKind::all().iter() {
+            let mut order = super::face_sticker_order(*face);
+            assert!(order.iter().all(|i| element_faces(*i).contains(face)));
+
+            order.sort();
+            let mut expected: Vec<usize> = (0..POSITIONS.len())
+                .filter(|i| element_faces(*i).contains(face))
+                .collect();
+            expected.sort();
+            assert_eq!(order.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn rotations_reorient_face_moves() {
+        for (rotated, expected) in &[
+            ("x U x'", "F"),
+            ("x' U x", "B"),
+            ("y R y'", "B"),
+            ("z U z'", "L"),
+            ("x2 R' D x2", "R' U"),
+        ] {
+            let rotated =
+                Cube::from_moves_iter(Move::parse_sequence(rotated).unwrap());
+            let expected =
+                Cube::from_moves_iter(Move::parse_sequence(expected).unwrap());
+
+            assert_eq!(rotated.facelets(), expected.facelets());
+        }
+    }
+
+    #[test]
+    fn compare_with_facelets() {
+        let mut expected = [Color::Uninit; 54];
+        let faces = [
+            Color::White,
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Orange,
+            Color::Blue,
+        ];
+        for (i, color) in expected.iter_mut().enumerate() {
+            *color = faces[i / 9];
+        }
+
+        let mut cube = Cube::solved();
+        assert_eq!(cube, expected);
+
+        cube.apply(Move::U);
+        assert_ne!(cube, expected);
+        assert_eq!(cube, cube.facelets());
+        assert!(format!("{:?}", cube).starts_with("Cube(WWWWWWWWWBBBRRR"));
+    }
+
+    #[test]
+    fn solve_timeout() {
+        let mut cube = Cube::solved();
+        cube.scramble(25, &mut StdRng::seed_from_u64(686));
+
+        assert_eq!(cube.solve_within(Duration::from_millis(10)), None);
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+
+        let solution = cube.solve_within(Duration::from_secs(60)).unwrap();
+        assert_eq!(solution, Move::parse_sequence("F' U' R'").unwrap());
+    }
+
+    #[test]
+    fn pieces_in_place() {
+        let mut cube = Cube::solved();
+        let corners: Vec<usize> = (0..cube.elements.len())
+            .filter(|i| element_faces(*i).len() == 3)
+            .collect();
+
+        cube.elements[corners[0]].face_slice_mut().rotate_left(1);
+        cube.elements[corners[5]].face_slice_mut().rotate_right(1);
+
+        assert!(!cube.is_solved());
+        assert_eq!(cube.pieces_in_correct_position(), 20);
+
+        cube.apply_sequence(&Move::parse_sequence("R2 U2").unwrap());
+        assert_eq!(cube.pieces_in_correct_position(), 7);
+
+        cube.apply(Move::Y);
+        assert_eq!(cube.pieces_in_correct_position(), 7);
+    }
+
+    #[test]
+    fn streamed_moves() {
+        let mut rng = StdRng::seed_from_u64(689);
+        let moves = Cube::solved().scramble(1000, &mut rng);
+
+        let mut cube = Cube::solved();
+        let mut calls = 0;
+        cube.apply_stream(moves.iter().copied(), 100, |applied| {
+            calls += 1;
+            assert_eq!(applied, calls * 100);
+        });
+
+        let mut expected = Cube::solved();
+        expected.apply_sequence(&moves);
+
+        assert_eq!(calls, 10);
+        assert_eq!(cube.facelets(), expected.facelets());
+    }
+
+    #[test]
+    fn face_permutations() {
+        for face in FaceKind::all().iter() {
+            let permutation = face_permutation(*face);
+            let mut composed: Vec<usize> = (0..26).collect();
+
+            for _ in 0..4 {
+                composed = composed.iter().map(|i| permutation[*i]).collect();
+
+                let mut sorted = composed.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..26).collect::<Vec<usize>>());
+            }
+
+            assert_eq!(composed, (0..26).collect::<Vec<usize>>());
+
+            let mut cube = Cube::solved();
+            cube.apply(Move::new(face.layer(), Direction::Clockwise));
+
+            for (i, j) in permutation.iter().enumerate() {
+                let mut before: Vec<Color> = Cube::solved().elements[i]
+                    .face_slice()
+                    .iter()
+                    .map(|f| f.color)
+                    .collect();
+                let mut after: Vec<Color> = cube.elements[*j]
+                    .face_slice()
+                    .iter()
+                    .map(|f| f.color)
+                    .collect();
+                before.sort_unstable();
+                after.sort_unstable();
+
+                assert_eq!(before, after);
+            }
+        }
+    }
+
+    #[test]
+    fn target_solved_states() {
+        let moves = Move::parse_sequence("R U2 F' L D").unwrap();
+
+        let mut western = Cube::solved();
+        western.apply_sequence(&moves);
+        assert_eq!(
+            western.target_solved().facelets(),
+            Cube::solved().facelets()
+        );
+
+        let japanese = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+        let mut scrambled = japanese.clone();
+        scrambled.apply_sequence(&moves);
+
+        let target = scrambled.target_solved();
+        assert_eq!(target.facelets(), japanese.facelets());
+        assert_ne!(target.facelets(), Cube::solved().facelets());
+        assert_eq!(target.face_colors(FaceKind::Back), [Color::Yellow; 9]);
+        assert_eq!(target.face_colors(FaceKind::Bottom), [Color::Blue; 9]);
+
+        let mut rotated = Cube::solved();
+        rotated.apply(Move::X);
+        assert_eq!(rotated.target_solved().facelets(), rotated.facelets());
+    }
+
+    #[test]
+    fn apply_reports() {
+        let mut cube = Cube::solved();
+        let report = cube.apply_and_report(&[Move::R; 4]);
+
+        assert_eq!(report, ApplyReport {
+            applied: 4,
+            cancelled: 4,
+            solved: true,
+        });
+
+        let moves = Move::parse_sequence("R U R' U'").unwrap();
+        let report = cube.apply_and_report(&moves);
+
+        assert_eq!(report.cancelled, 0);
+        assert!(!report.solved);
+    }
+
+    #[test]
+    fn padded_display() {
+        let cube = Cube::solved();
+        let plain = cube.to_string();
+
+        let padded = format!("{:width$}", cube, width = 40);
+        assert_eq!(padded.lines().count(), 9);
+        assert!(padded.lines().all(|l| l.len() == 40));
+        assert_eq!(padded.lines().next().unwrap().trim_end(), "      W W W");
+
+        let right = format!("{:*>25}", cube);
+        assert!(right.lines().all(|l| l.starts_with("**")));
+        assert_eq!(right.replace("**", ""), plain);
+
+        let narrow = format!("{:10}", cube);
+        assert!(narrow.lines().all(|l| l.len() == 23));
+        assert_eq!(narrow.lines().nth(4), plain.lines().nth(4));
+    }
+
+    #[test]
+    fn permutations_across_schemes() {
+        let moves =
+            Cube::solved().scramble(25, &mut StdRng::seed_from_u64(695));
+
+        let mut western = Cube::solved();
+        western.apply_sequence(&moves);
+
+        let mut japanese = Cube::solved().with_scheme(&ColorScheme::JAPANESE);
+        japanese.apply_sequence(&moves);
+
+        assert_ne!(western.facelets(), japanese.facelets());
+        assert!(western.is_permutation_of(&japanese));
+        assert!(japanese.is_permutation_of(&western));
+
+        japanese.apply(Move::U);
+        assert!(!western.is_permutation_of(&japanese));
+
+        let mut rotated = Cube::solved();
+        rotated.apply(Move::X);
+        assert!(rotated.is_permutation_of(&Cube::solved()));
+    }
+
+    #[test]
+    fn pieces_constructor() {
+        let solved = Cube::solved();
+        let corner_positions = [
+            CornerPosition::TopBackLeft,
+            CornerPosition::TopBackRight,
+            CornerPosition::TopFrontLeft,
+            CornerPosition::TopFrontRight,
+            CornerPosition::BottomBackLeft,
+            CornerPosition::BottomBackRight,
+            CornerPosition::BottomFrontLeft,
+            CornerPosition::BottomFrontRight,
+        ];
+        let edge_positions = [
+            EdgePosition::TopBack,
+            EdgePosition::TopLeft,
+            EdgePosition::TopRight,
+            EdgePosition::TopFront,
+            EdgePosition::MiddleBackLeft,
+            EdgePosition::MiddleBackRight,
+            EdgePosition::MiddleFrontLeft,
+            EdgePosition::MiddleFrontRight,
+            EdgePosition::BottomBack,
+            EdgePosition::BottomLeft,
+            EdgePosition::BottomRight,
+            EdgePosition::BottomFront,
+        ];
+        let colors = |cubie: &Box<dyn Cubie>| -> Vec<Color> {
+            cubie.face_slice().iter().map(|f| f.color).collect()
+        };
+
+        let mut corners =
+            [(CornerPosition::TopBackLeft, [Color::Uninit; 3], 0); 8];
+        for (corner, pos) in corners.iter_mut().zip(&corner_positions) {
+            corner.0 = *pos;
+            corner.1.copy_from_slice(&colors(solved.corner(*pos)));
+        }
+
+        let mut edges = [(EdgePosition::TopBack, [Color::Uninit; 2], 0); 12];
+        for (edge, pos) in edges.iter_mut().zip(&edge_positions) {
+            edge.0 = *pos;
+            edge.1.copy_from_slice(&colors(solved.edge(*pos)));
+        }
+
+        assert!(Cube::from_pieces(corners, edges).unwrap().is_solved());
+
+        corners[0].2 = 1;
+        assert_eq!(
+            Cube::from_pieces(corners, edges).err(),
+            Some(CubeError::TwistedCorner)
+        );
+
+        corners[7].2 = 2;
+        let cube = Cube::from_pieces(corners, edges).unwrap();
+        assert!(!cube.is_solved());
+        assert_eq!(cube.pieces_in_correct_position(), 20);
+        assert_eq!(cube.corner_orientations(), [1, 0, 0, 0, 0, 0, 0, 2]);
+
+        edges[0].2 = 2;
+        assert_eq!(
+            Cube::from_pieces(corners, edges).err(),
+            Some(CubeError::InvalidOrientation(2))
+        );
+    }
+
+    #[test]
+    fn successor_states() {
+        let mut cube = Cube::solved();
+        cube.scramble(10, &mut StdRng::seed_from_u64(698));
+
+        let successors = cube.successors();
+        assert_eq!(successors.len(), 18);
+
+        for (m, next) in successors.iter() {
+            assert_eq!(super::infer_move(&cube, next), Some(*m));
+            assert_eq!(cube.distance_to(next, 1), Some(1));
+        }
+    }
+
+    #[test]
+    fn self_check() {
+        assert_eq!(Cube::self_check(), Ok(()));
+    }
+
+    #[test]
+    fn setup_reaches_target() {
+        let mut rng = StdRng::seed_from_u64(702);
+
+        for _ in 0..5 {
+            let mut target = Cube::solved();
+            let scramble = target.scramble(4, &mut rng);
+
+            let setup = Cube::setup_for(&target, 4).unwrap();
+            assert!(setup.len() <= scramble.len());
+
+            let mut cube = Cube::solved();
+            cube.apply_sequence(&setup);
+            assert_eq!(cube.facelets(), target.facelets());
+        }
+
+        let mut target = Cube::solved();
+        target.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+        assert_eq!(Cube::setup_for(&target, 2), None);
+    }
+
+    #[test]
+    fn faces_and_colors() {
+        let cube = Cube::solved();
+
+        assert_eq!(cube.face_of_color(Color::White), Some(FaceKind::Top));
+        assert_eq!(cube.color_of_face(FaceKind::Front), Color::Green);
+        assert_eq!(cube.face_of_color(Color::Uninit), None);
+
+        for face in FaceKind::all().iter() {
+            assert_eq!(
+                cube.face_of_color(cube.color_of_face(*face)),
+                Some(*face)
+            );
+        }
+
+        let japanese = cube.with_scheme(&ColorScheme::JAPANESE);
+        assert_eq!(japanese.face_of_color(Color::Blue), Some(FaceKind::Bottom));
+        assert_eq!(japanese.color_of_face(FaceKind::Back), Color::Yellow);
+    }
+
+    #[test]
+    fn scrambles_avoid_redundant_axis_turns() {
+        let mut rng = StdRng::seed_from_u64(704);
+
+        for _ in 0..200 {
+            let scramble = Cube::solved().scramble(25, &mut rng);
+            assert_not_redundant(&scramble);
+        }
+    }
+
+    #[test]
+    fn unfolded_stickers() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U' F2").unwrap());
+
+        let layout = cube.unfolded_layout();
+        assert_eq!(layout.len(), 54);
+
+        let cells: HashSet<(u32, u32)> =
+            layout.iter().map(|s| (s.x, s.y)).collect();
+        assert_eq!(cells.len(), 54);
+
+        let grid = cube.net_grid();
+        for sticker in layout.iter() {
+            assert!(sticker.x < 12 && sticker.y < 9);
+            assert_eq!(
+                grid[sticker.y as usize][sticker.x as usize],
+                Some(sticker.color)
+            );
+        }
+
+        let front: Vec<Color> = layout
+            .iter()
+            .filter(|s| s.face == FaceKind::Front)
+            .map(|s| s.color)
+            .collect();
+        assert_eq!(front, cube.face_colors(FaceKind::Front).to_vec());
+    }
+
+    #[test]
+    fn move_permutation_table() {
+        let identity: Vec<usize> = (0..54).collect();
+
+        for (permutation, m) in MOVE_PERMUTATIONS.iter().zip(Move::all_basic())
+        {
+            let mut sorted = permutation.to_vec();
+            sorted.sort_unstable();
+            assert_eq!(sorted, identity);
+
+            let order = match m.direction {
+                Direction::Double => 2,
+                _ => 4,
+            };
+            let mut composed = identity.clone();
+            for n in 1..=order {
+                composed = composed.iter().map(|i| permutation[*i]).collect();
+                assert_eq!(composed == identity, n == order, "{}", m);
+            }
+
+            let mut cube = Cube::solved();
+            cube.scramble(10, &mut StdRng::seed_from_u64(706));
+            let before = cube.facelets();
+            cube.apply(m);
+
+            let expected: Vec<Color> =
+                permutation.iter().map(|i| before[*i]).collect();
+            assert_eq!(cube.facelets().to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn solvable() {
+        let mut rng = StdRng::seed_from_u64(707);
+        let mut cube = Cube::solved();
+        cube.scramble(30, &mut rng);
+        assert!(cube.is_solvable());
+
+        let edge = element_index((0, 1, 1));
+        cube.elements[edge].face_slice_mut().swap(0, 1);
+        assert!(!cube.is_solvable());
+    }
+
+    #[test]
+    fn into_owned_cubies() {
+        let mut cube = Cube::solved();
+        cube.apply(Move::R);
+        let color = cube.elements[0].face_slice()[0].color;
+
+        let cubies: Vec<Box<dyn Cubie>> = cube.into_iter().collect();
+
+        assert_eq!(cubies.len(), 26);
+        assert_eq!(cubies[0].face_slice()[0].color, color);
+    }
+
+    #[test]
+    fn progress_comparison() {
+        let mut rng = StdRng::seed_from_u64(713);
+        let mut scrambled = Cube::solved();
+        scrambled.scramble(25, &mut rng);
+
+        let solved = Cube::solved();
+        assert_eq!(solved.compare_progress(&scrambled), Ordering::Greater);
+        assert_eq!(scrambled.compare_progress(&solved), Ordering::Less);
+
+        let mut sexy = Cube::solved();
+        sexy.apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+        let mut sune = Cube::solved();
+        sune.apply_sequence(&Move::parse_sequence("R U R' U R U2 R'").unwrap());
+        assert_eq!(sexy.pieces_in_correct_position(), 13);
+        assert_eq!(sune.pieces_in_correct_position(), 13);
+        assert_eq!(sexy.compare_progress(&sune), Ordering::Greater);
+    }
+
+    #[test]
+    fn thread_safe() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+
+        assert_send::<Cube>();
+        assert_sync::<Cube>();
+    }
+
+    #[test]
+    fn sexy_move_order() {
+        let mut c = Cube::solved();
+        let sexy = Move::parse_sequence("R U R' U'").unwrap();
+
+        for i in 0..6 {
+            assert_eq!(c.is_solved(), i == 0);
+            c.apply_sequence(&sexy);
+        }
+
+        assert!(c.is_solved());
+    }
+
+    #[test]
+    fn normalize_orientation() {
+        for rotations in &["x", "y'", "z", "x2 y", "z' y2", "x y z"] {
+            let mut c = Cube::solved();
+            c.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+            c.apply_sequence(&Move::parse_sequence(rotations).unwrap());
+
+            let applied = c.normalize_orientation();
+            assert!(applied.len() <= 2);
+
+            assert_eq!(c.face_colors(FaceKind::Top)[4], Color::White);
+            assert_eq!(c.face_colors(FaceKind::Front)[4], Color::Green);
+        }
+    }
+
+    #[test]
+    fn color_histogram() {
+        let mut c = Cube::solved();
+        assert_eq!(c.color_histogram(), [9, 9, 9, 9, 9, 9, 0]);
+
+        c.apply_sequence(&Move::parse_sequence("R U F' M2 x").unwrap());
+        assert_eq!(c.color_histogram(), [9, 9, 9, 9, 9, 9, 0]);
+
+        let c = Cube::new();
+        assert_eq!(c.color_histogram(), [0, 0, 0, 0, 0, 0, 54]);
+    }
+
+    #[test]
+    fn chained_moves() {
+        let mut c = Cube::solved();
+
+        c.apply(Move::R)
+            .apply(Move::U)
+            .apply(Move::U.inverse())
+            .apply(Move::R.inverse());
+        assert!(c.is_solved());
+
+        c.rotate_face_cw(FaceKind::Front)
+            .apply_sequence(&Move::parse_sequence("U L").unwrap())
+            .apply_sequence(&Move::parse_sequence("L' U'").unwrap())
+            .rotate_face_ccw(FaceKind::Front);
+        assert!(c.is_solved());
+    }
+
+    #[test]
+    fn from_moves_iter() {
+        let c = Cube::from_moves_iter(std::iter::repeat(Move::R).take(4));
+        assert!(c.is_solved());
+
+        let moves = Move::parse_sequence("R U F D L B").unwrap();
+        let c = Cube::from_moves_iter(
+            moves
+                .iter()
+                .copied()
+                .filter(|m| m.layer != Layer::U && m.layer != Layer::D),
+        );
+        let mut expected = Cube::solved();
+        expected.apply_sequence(&Move::parse_sequence("R F L B").unwrap());
+        assert_eq!(c.facelets(), expected.facelets());
+    }
+
+    #[test]
+    fn scramble() {
+        let mut rng = StdRng::seed_from_u64(615);
+        let mut c = Cube::solved();
+
+        let moves = c.scramble(30, &mut rng);
+        assert_eq!(moves.len(), 30);
+        assert!(!c.is_solved());
+
+        for pair in moves.windows(2) {
+            assert_ne!(pair[0].layer, pair[1].layer);
+        }
+
+        c.apply_sequence(&Sequence(moves).inverse().0);
+        assert!(c.is_solved());
+    }
+
+    #[test]
+    fn apply_random_move() {
+        let mut rng = StdRng::seed_from_u64(637);
+        let mut c = Cube::solved();
+        c.scramble(10, &mut rng);
+
+        for _ in 0..20 {
+            let before = c.clone();
+            let m = c.apply_random_move(&mut rng);
+
+            assert!(Move::all_basic().any(|basic| basic == m));
+
+            c.apply(m.inverse());
+            assert_eq!(c.facelets(), before.facelets());
+        }
+    }
+
+    #[test]
+    fn scramble_and_solve() {
+        let mut rng = StdRng::seed_from_u64(627);
+        let solver = OptimalSolver::new(4);
+
+        for _ in 0..3 {
+            let (scramble, solution) =
+                Cube::scramble_and_solve(4, &mut rng, &solver);
+
+            let mut c = Cube::solved();
+            c.apply_sequence(&scramble);
+            assert!(!c.is_solved());
+
+            c.apply_sequence(&solution);
+            assert!(c.is_solved());
+        }
+    }
+
+    #[test]
+    fn apply_sequence_optimized() {
+        let moves =
+            Move::parse_sequence("R R' U2 F F F D' D B2 L L2 R U U U U")
+                .unwrap();
+
+        let mut full = Cube::solved();
+        full.apply_sequence(&moves);
+
+        let mut optimized = Cube::solved();
+        let applied = optimized.apply_sequence_optimized(&moves);
+
+        assert!(applied.len() < moves.len());
+        assert_eq!(optimized.facelets(), full.facelets());
+    }
+
+    #[test]
+    fn apply_sequence_hook() {
+        let moves = Move::parse_sequence("R U R' U'").unwrap();
+        let mut c = Cube::solved();
+        let mut calls = Vec::new();
+
+        c.apply_sequence_with(&moves, |m, cube| {
+            calls.push((*m, cube.is_solved()));
+        });
+
+        assert_eq!(calls, vec![
+            (Move::R, false),
+            (Move::U, false),
+            (Move::R_PRIME, false),
+            (Move::U_PRIME, false),
+        ]);
+    }
+
+    #[test]
+    fn facelet_strings() {
+        let c = Cube::solved();
+
+        assert_eq!(
+            c.to_kociemba(),
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"
+        );
+        assert_eq!(
+            c.to_color_string(),
+            "WWWWWWWWWRRRRRRRRRGGGGGGGGGYYYYYYYYYOOOOOOOOOBBBBBBBBB"
+        );
+
+        let mut c = Cube::solved();
+        c.apply_sequence(&Move::parse_sequence("R U F' L2 D B'").unwrap());
+
+        let kociemba: Vec<char> = c.to_kociemba().chars().collect();
+        let colors: Vec<char> = c.to_color_string().chars().collect();
+
+        // Both strings label the same stickers, only the alphabet differs
+        for (k, color) in kociemba.iter().zip(&colors) {
+            let expected = match k {
+                'U' => 'W',
+                'R' => 'R',
+                'F' => 'G',
+                'D' => 'Y',
+                'L' => 'O',
+                'B' => 'B',
+                _ => panic!("Unexpected face letter"),
+            };
+            assert_eq!(*color, expected);
+        }
+        assert_ne!(kociemba, colors);
+    }
+
+    #[test]
+    fn recolor() {
+        let mut c = Cube::solved();
+        c.apply_sequence(&Move::parse_sequence("R U F'").unwrap());
+
+        c.recolor(Color::White, Color::Red);
+        assert_eq!(c.color_histogram(), [9, 9, 9, 18, 0, 9, 0]);
+
+        c.recolor(Color::Yellow, Color::Uninit);
+        assert_eq!(c.color_histogram(), [9, 9, 9, 18, 0, 0, 9]);
+    }
+
+    #[test]
+    fn first_difference() {
+        let solved = Cube::solved();
+        let mut c = Cube::solved();
+        assert_eq!(c.first_difference(&solved), None);
+
+        c.apply(Move::R);
+        assert_eq!(c.first_difference(&solved), Some(2));
+        assert_eq!(solved.first_difference(&c), Some(2));
+
+        c.apply(Move::R_PRIME).apply(Move::D);
+        assert_eq!(c.first_difference(&solved), Some(17));
+    }
+
+    #[test]
+    fn f2l_pairs() {
+        let mut c = Cube::solved();
+        assert_eq!(c.f2l_pairs_solved(), 4);
+
+        // Turning the top face keeps the first two layers intact
+        c.apply(Move::U);
+        assert_eq!(c.f2l_pairs_solved(), 4);
+
+        c.apply_sequence(&Move::parse_sequence("R F' L2 D B").unwrap());
+        assert!(c.f2l_pairs_solved() < 4);
+
+        // A rotation keeps the pairs solved relative to the centers
+        let mut c = Cube::solved();
+        c.apply(Move::Y);
+        assert_eq!(c.f2l_pairs_solved(), 4);
     }
 }
@@ -2,7 +2,10 @@ use staticvec::{staticvec, StaticVec};
 use std::any::Any;
 
 /// Standard colors for 6 sided twisty puzzles, plus an uninitialized value.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///
+/// Colors are ordered the same way they are declared, so sorting a cubie's
+/// colors gives the same order no matter which way the cubie is twisted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Color {
     Blue,
     Green,
@@ -18,6 +21,144 @@ impl Color {
         Color::Uninit
     }
 
+    /// Returns the color at an index in declaration order, which is the
+    /// inverse of casting a color to an integer, or None if the index is too
+    /// large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// assert_eq!(Color::from_index(Color::Red as u8), Some(Color::Red));
+    /// assert_eq!(Color::from_index(7), None);
+    /// ```
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(Color::Blue),
+            1 => Some(Color::Green),
+            2 => Some(Color::Orange),
+            3 => Some(Color::Red),
+            4 => Some(Color::White),
+            5 => Some(Color::Yellow),
+            6 => Some(Color::Uninit),
+            _ => None,
+        }
+    }
+
+    /// Returns the single letter used for the color in facelet strings, or `-`
+    /// for an uninitialized color.
+    pub const fn letter(&self) -> char {
+        match self {
+            Color::Blue => 'B',
+            Color::Green => 'G',
+            Color::Orange => 'O',
+            Color::Red => 'R',
+            Color::White => 'W',
+            Color::Yellow => 'Y',
+            Color::Uninit => '-',
+        }
+    }
+
+    /// Returns the color with a letter, as given by [`Color::letter`], or None
+    /// if no color uses the letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// assert_eq!(Color::from_letter('W'), Some(Color::White));
+    /// assert_eq!(Color::from_letter('w'), None);
+    /// ```
+    pub const fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'B' => Some(Color::Blue),
+            'G' => Some(Color::Green),
+            'O' => Some(Color::Orange),
+            'R' => Some(Color::Red),
+            'W' => Some(Color::White),
+            'Y' => Some(Color::Yellow),
+            '-' => Some(Color::Uninit),
+            _ => None,
+        }
+    }
+
+    /// Returns the RGB color of text which stays readable when drawn over a
+    /// sticker of this color: black over the light colors, and white over the
+    /// dark ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// assert_eq!(Color::Yellow.text_color(), (0, 0, 0));
+    /// assert_eq!(Color::Blue.text_color(), (255, 255, 255));
+    /// ```
+    pub const fn text_color(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Orange | Color::White | Color::Yellow => (0, 0, 0),
+            Color::Blue | Color::Green | Color::Red | Color::Uninit => {
+                (255, 255, 255)
+            }
+        }
+    }
+
+    /// Returns the RGB color to draw a sticker of this color with in a palette.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// assert_eq!(
+    ///     Color::White.to_rgb_scheme(PaletteScheme::Standard),
+    ///     (255, 255, 255)
+    /// );
+    /// ```
+    pub const fn to_rgb_scheme(&self, scheme: PaletteScheme) -> (u8, u8, u8) {
+        match scheme {
+            PaletteScheme::Standard => match self {
+                Color::Blue => (0, 70, 173),
+                Color::Green => (0, 155, 72),
+                Color::Orange => (255, 88, 0),
+                Color::Red => (183, 18, 52),
+                Color::White => (255, 255, 255),
+                Color::Yellow => (255, 213, 0),
+                Color::Uninit => (128, 128, 128),
+            },
+            PaletteScheme::ColorBlindSafe => match self {
+                Color::Blue => (0, 60, 180),
+                Color::Green => (0, 158, 115),
+                Color::Orange => (230, 159, 0),
+                Color::Red => (170, 30, 80),
+                Color::White => (255, 255, 255),
+                Color::Yellow => (240, 228, 66),
+                Color::Uninit => (128, 128, 128),
+            },
+        }
+    }
+
+    /// Blends the standard palette's RGB colors for two colors, moving from
+    /// `a` at a `t` of 0 to `b` at a `t` of 1. Values of `t` outside of that
+    /// range are clamped to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// assert_eq!(
+    ///     Color::lerp_rgb(Color::White, Color::Blue, 0.5),
+    ///     (128, 163, 214)
+    /// );
+    /// ```
+    pub fn lerp_rgb(a: Color, b: Color, t: f32) -> (u8, u8, u8) {
+        let t = t.max(0.0).min(1.0);
+        let (r1, g1, b1) = a.to_rgb_scheme(PaletteScheme::Standard);
+        let (r2, g2, b2) = b.to_rgb_scheme(PaletteScheme::Standard);
+        let lerp =
+            |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+
+        (lerp(r1, r2), lerp(g1, g2), lerp(b1, b2))
+    }
+
     /// Returns the standardized opposite color of a Color instance
     pub const fn opposite_color(&self) -> Self {
         Color::opposite_color_from_color(&self)
@@ -37,6 +178,63 @@ impl Color {
     }
 }
 
+/// Colors are serialized as a string of their single
+/// [letter](Color::letter) to keep serialized cubes small. It's written as a
+/// string rather than a char so that it reads back the same way in formats
+/// which aren't self-describing.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.letter().to_string())
+    }
+}
+
+/// Colors are deserialized from either their single letter or their full
+/// name, such as `"W"` or `"White"`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let mut chars = s.chars();
+
+        let color = match (chars.next(), chars.next()) {
+            (Some(letter), None) => Color::from_letter(letter),
+            _ => match s.as_str() {
+                "Blue" => Some(Color::Blue),
+                "Green" => Some(Color::Green),
+                "Orange" => Some(Color::Orange),
+                "Red" => Some(Color::Red),
+                "White" => Some(Color::White),
+                "Yellow" => Some(Color::Yellow),
+                "Uninit" => Some(Color::Uninit),
+                _ => None,
+            },
+        };
+
+        color.ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&s),
+                &"a color letter or name",
+            )
+        })
+    }
+}
+
+/// Palettes of RGB colors which [`Color`]s can be drawn with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PaletteScheme {
+    /// The usual colors of cube stickers.
+    Standard,
+    /// Colors which stay distinguishable with common color blindness, by
+    /// pulling red and orange apart and moving green towards teal.
+    ColorBlindSafe,
+}
+
 #[derive(Copy, Clone, Debug, Eq)]
 pub struct Face {
     pub color: Color,
@@ -75,6 +273,10 @@ impl Face {
 /// their cubie kind, this trait provides a method which all cubie types
 /// implement to cast it as Any.
 ///
+/// Cubies must be `Send` and `Sync`, so that a
+/// [`Cube`](crate::cube::cube::Cube) holding boxed cubies can be moved to or
+/// shared with other threads, such as when solving several cubes at once.
+///
 /// # Examples
 ///
 /// ```
@@ -86,8 +288,32 @@ impl Face {
 ///     None => panic!("&b isn't a Center!!"),
 /// };
 /// ```
-pub trait Cubie {
+pub trait Cubie: Send + Sync {
     fn as_any(&self) -> &dyn Any;
+
+    /// Returns a boxed copy of the cubie, allowing boxed cubies to be cloned.
+    fn clone_box(&self) -> Box<dyn Cubie>;
+
+    /// Returns the cubie's faces as a slice, regardless of the cubie kind.
+    fn face_slice(&self) -> &[Face];
+
+    /// Returns the cubie's faces as a mutable slice, regardless of the cubie
+    /// kind.
+    fn face_slice_mut(&mut self) -> &mut [Face];
+
+    /// Returns true if every face of the cubie has been given a color, rather
+    /// than being left [`Color::Uninit`].
+    fn faces_are_complete(&self) -> bool {
+        self.face_slice()
+            .iter()
+            .all(|face| face.color != Color::Uninit)
+    }
+}
+
+impl Clone for Box<dyn Cubie> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
 
 #[derive(Clone, Debug, Eq)]
@@ -105,6 +331,18 @@ impl Cubie for Center {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Cubie> {
+        Box::new(self.clone())
+    }
+
+    fn face_slice(&self) -> &[Face] {
+        self.faces.as_slice()
+    }
+
+    fn face_slice_mut(&mut self) -> &mut [Face] {
+        self.faces.as_mut_slice()
+    }
 }
 
 impl Center {
@@ -133,29 +371,28 @@ impl Center {
     }
 
     pub fn new_from_vec(vec: Vec<Face>) -> Self {
-        if vec.len() == 0 {
+        Self::new_from_slice(&vec)
+    }
+
+    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
+        Box::new(Self::new_from_slice(&vec))
+    }
+
+    pub fn new_from_slice(slice: &[Face]) -> Self {
+        let cubie = if slice.is_empty() {
             Self::new()
         } else {
-            let mut v = vec.clone();
-            v.truncate(1);
-
             Self {
-                faces: StaticVec::from(v),
+                faces: StaticVec::new_from_slice(slice),
             }
-        }
-    }
+        };
 
-    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
-        if vec.len() == 0 {
-            Box::new(Self::new())
-        } else {
-            let mut v = vec.clone();
-            v.truncate(1);
+        debug_assert_eq!(cubie.faces.len(), 1);
+        cubie
+    }
 
-            Box::new(Self {
-                faces: StaticVec::from(v),
-            })
-        }
+    pub fn new_boxed_from_slice(slice: &[Face]) -> Box<Self> {
+        Box::new(Self::new_from_slice(slice))
     }
 
     pub const fn faces(&self) -> &StaticVec<Face, 1> {
@@ -178,6 +415,18 @@ impl Cubie for Corner {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Cubie> {
+        Box::new(self.clone())
+    }
+
+    fn face_slice(&self) -> &[Face] {
+        self.faces.as_slice()
+    }
+
+    fn face_slice_mut(&mut self) -> &mut [Face] {
+        self.faces.as_mut_slice()
+    }
 }
 
 impl Corner {
@@ -206,44 +455,61 @@ impl Corner {
     }
 
     pub fn new_from_vec(vec: Vec<Face>) -> Self {
-        let l = vec.len();
-        if l == 0 {
+        Self::new_from_slice(&vec)
+    }
+
+    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
+        Box::new(Self::new_from_slice(&vec))
+    }
+
+    pub fn new_from_slice(slice: &[Face]) -> Self {
+        let l = slice.len();
+        let cubie = if l == 0 {
             Self::new()
-        } else if (l > 0) && (l < 3) {
+        } else if l < 3 {
             Self {
-                faces: staticvec![vec[0]; 3],
+                faces: staticvec![slice[0]; 3],
             }
         } else {
-            let mut v = vec.clone();
-            v.truncate(1);
-
             Self {
-                faces: StaticVec::from(v),
+                faces: StaticVec::new_from_slice(slice),
             }
-        }
-    }
+        };
 
-    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
-        let l = vec.len();
-        if l == 0 {
-            Box::new(Self::new())
-        } else if (l > 0) && (l < 3) {
-            Box::new(Self {
-                faces: staticvec![vec[0]; 3],
-            })
-        } else {
-            let mut v = vec.clone();
-            v.truncate(3);
+        debug_assert_eq!(cubie.faces.len(), 3);
+        cubie
+    }
 
-            Box::new(Self {
-                faces: StaticVec::from(v),
-            })
-        }
+    pub fn new_boxed_from_slice(slice: &[Face]) -> Box<Self> {
+        Box::new(Self::new_from_slice(slice))
     }
 
     pub const fn faces(&self) -> &StaticVec<Face, 3> {
         &self.faces
     }
+
+    /// Twists the corner in place by moving each face along to the next
+    /// position in its faces a number of times, wrapping around from the last
+    /// position to the first. Twisting three times leaves the corner as it
+    /// was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::cubie::*;
+    /// let [r, g, b] = [Color::Red, Color::Green, Color::Blue];
+    /// let mut corner = Corner::new_from_array([
+    ///     Face::new_from_cubie_color(r),
+    ///     Face::new_from_cubie_color(g),
+    ///     Face::new_from_cubie_color(b),
+    /// ]);
+    ///
+    /// corner.twist(1);
+    /// assert_eq!(corner.faces()[0].color, b);
+    /// ```
+    pub fn twist(&mut self, amount: u8) {
+        self.faces.as_mut_slice().rotate_right(amount as usize % 3);
+    }
 }
 
 #[derive(Clone, Debug, Eq)]
@@ -261,6 +527,18 @@ impl Cubie for Edge {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Cubie> {
+        Box::new(self.clone())
+    }
+
+    fn face_slice(&self) -> &[Face] {
+        self.faces.as_slice()
+    }
+
+    fn face_slice_mut(&mut self) -> &mut [Face] {
+        self.faces.as_mut_slice()
+    }
 }
 
 impl Edge {
@@ -289,44 +567,43 @@ impl Edge {
     }
 
     pub fn new_from_vec(vec: Vec<Face>) -> Self {
-        let l = vec.len();
-        if l == 0 {
+        Self::new_from_slice(&vec)
+    }
+
+    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
+        Box::new(Self::new_from_slice(&vec))
+    }
+
+    pub fn new_from_slice(slice: &[Face]) -> Self {
+        let l = slice.len();
+        let cubie = if l == 0 {
             Self::new()
-        } else if (l > 0) && (l < 2) {
+        } else if l < 2 {
             Self {
-                faces: staticvec![vec[0]; 2],
+                faces: staticvec![slice[0]; 2],
             }
         } else {
-            let mut v = vec.clone();
-            v.truncate(1);
-
             Self {
-                faces: StaticVec::from(v),
+                faces: StaticVec::new_from_slice(slice),
             }
-        }
-    }
+        };
 
-    pub fn new_boxed_from_vec(vec: Vec<Face>) -> Box<Self> {
-        let l = vec.len();
-        if l == 0 {
-            Box::new(Self::new())
-        } else if (l > 0) && (l < 2) {
-            Box::new(Self {
-                faces: staticvec![vec[0]; 2],
-            })
-        } else {
-            let mut v = vec.clone();
-            v.truncate(1);
+        debug_assert_eq!(cubie.faces.len(), 2);
+        cubie
+    }
 
-            Box::new(Self {
-                faces: StaticVec::from(v),
-            })
-        }
+    pub fn new_boxed_from_slice(slice: &[Face]) -> Box<Self> {
+        Box::new(Self::new_from_slice(slice))
     }
 
     pub const fn faces(&self) -> &StaticVec<Face, 2> {
         &self.faces
     }
+
+    /// Flips the edge in place by swapping its two faces.
+    pub fn flip(&mut self) {
+        self.faces.as_mut_slice().swap(0, 1);
+    }
 }
 
 #[cfg(test)]
@@ -351,6 +628,95 @@ mod tests {
         assert_eq!(c3.0, c3.1)
     }
 
+    #[test]
+    fn color_ordering() {
+        let mut colors = [Color::Red, Color::Blue, Color::White];
+        colors.sort();
+
+        assert_eq!(colors, [Color::Blue, Color::Red, Color::White]);
+        assert!(Color::Yellow < Color::Uninit);
+    }
+
+    #[test]
+    fn text_color() {
+        let dark = (0, 0, 0);
+        let light = (255, 255, 255);
+
+        assert_eq!(Color::White.text_color(), dark);
+        assert_eq!(Color::Yellow.text_color(), dark);
+        assert_eq!(Color::Blue.text_color(), light);
+        assert_eq!(Color::Red.text_color(), light);
+    }
+
+    #[test]
+    fn color_blind_palette() {
+        let distance = |scheme| {
+            let (r1, g1, b1) = Color::Red.to_rgb_scheme(scheme);
+            let (r2, g2, b2) = Color::Orange.to_rgb_scheme(scheme);
+
+            [(r1, r2), (g1, g2), (b1, b2)]
+                .iter()
+                .map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        assert!(
+            distance(PaletteScheme::ColorBlindSafe)
+                > distance(PaletteScheme::Standard)
+        );
+    }
+
+    #[test]
+    fn blended_colors() {
+        let rgb = |c: Color| c.to_rgb_scheme(PaletteScheme::Standard);
+
+        assert_eq!(
+            Color::lerp_rgb(Color::Red, Color::Green, 0.0),
+            rgb(Color::Red)
+        );
+        assert_eq!(
+            Color::lerp_rgb(Color::Red, Color::Green, 1.0),
+            rgb(Color::Green)
+        );
+        assert_eq!(
+            Color::lerp_rgb(Color::Red, Color::Green, -2.0),
+            rgb(Color::Red)
+        );
+        assert_eq!(
+            Color::lerp_rgb(Color::Red, Color::Green, 3.0),
+            rgb(Color::Green)
+        );
+    }
+
+    #[test]
+    fn color_letters() {
+        for i in 0..7 {
+            let color = Color::from_index(i).unwrap();
+
+            assert_eq!(Color::from_letter(color.letter()), Some(color));
+        }
+
+        assert_eq!(Color::from_letter('X'), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_letters() {
+        let colors = vec![Color::Blue, Color::White, Color::Uninit];
+        let json = serde_json::to_string(&colors).unwrap();
+
+        assert_eq!(json, r#"["B","W","-"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Color>>(&json).unwrap(), colors);
+
+        let named: Vec<Color> =
+            serde_json::from_str(r#"["Blue", "W", "Orange"]"#).unwrap();
+        assert_eq!(named, vec![Color::Blue, Color::White, Color::Orange]);
+
+        assert!(serde_json::from_str::<Color>(r#""Purple""#).is_err());
+        assert!(serde_json::from_str::<Color>(r#""w""#).is_err());
+    }
+
     #[test]
     fn center_cubie() {
         let a: Box<dyn Cubie> = cubie!("center");
@@ -397,6 +763,99 @@ mod tests {
 
         // truncation works for larger cubie types
         assert_eq!(a.faces.len(), 3);
+
+        let a = Corner::new_from_vec(vec![Face::new(); 4]);
+
+        // unboxed corners are truncated to three faces too
+        assert_eq!(a.faces.len(), 3);
+
+        let a = Edge::new_from_vec(vec![Face::new(); 2]);
+
+        // edges keep both of their faces
+        assert_eq!(a.faces.len(), 2);
+
+        let a = Edge::new_boxed_from_vec(vec![Face::new(); 5]);
+
+        // edges are truncated to two faces
+        assert_eq!(a.faces.len(), 2);
+
+        let red = Face::new_from_cubie_color(Color::Red);
+        let blue = Face::new_from_cubie_color(Color::Blue);
+        let a = Edge::new_from_vec(vec![red, blue, red]);
+
+        // truncation keeps the leading faces
+        assert_eq!(a.faces[..], [red, blue]);
+    }
+
+    #[test]
+    fn faces_are_complete() {
+        let red = Face::new_from_cubie_color(Color::Red);
+        let blue = Face::new_from_cubie_color(Color::Blue);
+
+        assert!(Edge::new_from_array([red, blue]).faces_are_complete());
+        assert!(!Edge::new_from_array([red, Face::new()]).faces_are_complete());
+        assert!(!Corner::new().faces_are_complete());
+        assert!(Corner::new_from_vec(vec![red; 3]).faces_are_complete());
+    }
+
+    #[test]
+    fn twist_and_flip() {
+        let red = Face::new_from_cubie_color(Color::Red);
+        let green = Face::new_from_cubie_color(Color::Green);
+        let blue = Face::new_from_cubie_color(Color::Blue);
+
+        let corner = Corner::new_from_array([red, green, blue]);
+
+        let mut twisted = corner.clone();
+        twisted.twist(3);
+        assert_eq!(twisted, corner);
+
+        twisted.twist(1);
+        assert_eq!(twisted.faces[..], [blue, red, green]);
+        twisted.twist(1);
+        assert_eq!(twisted.faces[..], [green, blue, red]);
+        twisted.twist(4);
+        assert_eq!(twisted, corner);
+
+        let mut edge = Edge::new_from_array([red, blue]);
+        edge.flip();
+        assert_eq!(edge.faces[..], [blue, red]);
+        edge.flip();
+        assert_eq!(edge.faces[..], [red, blue]);
+    }
+
+    #[test]
+    fn new_from_slice() {
+        let a = Center::new_boxed_from_slice(&[Face::new(); 1]);
+
+        // new_from_slice works with good value
+        assert_eq!(a.faces.len(), 1);
+
+        let a = Center::new_boxed_from_slice(&[Face::new(); 10]);
+
+        // too big value is truncated
+        assert_eq!(a.faces.len(), 1);
+
+        let a = Center::new_boxed_from_slice(&[]);
+
+        // too little value means values are added onto faces
+        assert_eq!(a.faces.len(), 1);
+
+        let a = Corner::new_boxed_from_slice(&[Face::new(); 1]);
+
+        // values added on to correct cubie face number
+        assert_eq!(a.faces.len(), 3);
+
+        let a = Corner::new_boxed_from_slice(&[Face::new(); 4]);
+
+        // truncation works for larger cubie types
+        assert_eq!(a.faces.len(), 3);
+
+        let red = Face::new_from_cubie_color(Color::Red);
+        let a = Edge::new_from_slice(&[red]);
+
+        // padding repeats the first face
+        assert_eq!(a.faces[..], [red, red]);
     }
 
     #[test]
@@ -0,0 +1,282 @@
+use crate::cube::cube::{facelet_permutation, Cube, MOVE_PERMUTATIONS};
+use crate::cube::cubie::Color;
+use crate::cube::moves::{Layer, Move, DIRECTIONS, FACE_LAYERS};
+
+/// The most facelets a single layer turn changes: the eight outer stickers
+/// of a face and the twelve around its edge.
+const MAX_MOVED: usize = 20;
+
+/// The facelets a permutation changes, as pairs of the index changed and the
+/// index its color comes from. Permutations which change fewer than
+/// [`MAX_MOVED`] facelets are padded with pairs which leave facelet 0 where it
+/// is.
+type MovedFacelets = [(usize, usize); MAX_MOVED];
+
+/// The facelets changed by each of the 18 basic moves, in the order of
+/// [`MOVE_PERMUTATIONS`].
+const MOVED_FACELETS: [MovedFacelets; 18] = moved_facelet_table();
+
+/// The facelets changed by a clockwise quarter turn of each slice, in the
+/// order M, E, S.
+const SLICE_MOVED_FACELETS: [MovedFacelets; 3] = [
+    moved_facelets(facelet_permutation(Layer::M)),
+    moved_facelets(facelet_permutation(Layer::E)),
+    moved_facelets(facelet_permutation(Layer::S)),
+];
+
+const fn moved_facelet_table() -> [MovedFacelets; 18] {
+    let mut table = [[(0, 0); MAX_MOVED]; 18];
+    let mut m = 0;
+
+    while m < MOVE_PERMUTATIONS.len() {
+        table[m] = moved_facelets(MOVE_PERMUTATIONS[m]);
+        m += 1;
+    }
+
+    table
+}
+
+const fn moved_facelets(permutation: [usize; 54]) -> MovedFacelets {
+    let mut moved = [(0, 0); MAX_MOVED];
+    let mut n = 0;
+    let mut i = 0;
+
+    while i < 54 {
+        if permutation[i] != i {
+            moved[n] = (i, permutation[i]);
+            n += 1;
+        }
+        i += 1;
+    }
+
+    moved
+}
+
+/// The colors of the 54 stickers of a cube, in the same order as
+/// [`Cube::facelets`].
+///
+/// Facelets can only be turned and compared, but they turn much faster than a
+/// [`Cube`] since every move is a permutation of a plain array, which makes
+/// them better suited to search code. Each layer turn only touches the
+/// facelets it moves. The two representations convert into
+/// each other with [`From`], so a cube can be searched as facelets and the
+/// result converted back, without any change to its state. The `apply`
+/// benchmark compares how quickly each representation turns.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, facelets::*, moves::*};
+/// let mut cube = Cube::solved();
+/// let mut facelets = Facelets::from(&cube);
+///
+/// cube.apply(Move::R);
+/// facelets.apply(Move::R);
+///
+/// assert_eq!(facelets, Facelets::from(&cube));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Facelets(pub [Color; 54]);
+
+impl Facelets {
+    /// Returns the facelets of a solved cube.
+    pub fn solved() -> Self {
+        Self::from(&Cube::solved())
+    }
+
+    /// Applies a [`Move`] to the facelets.
+    pub fn apply(&mut self, m: Move) -> &mut Self {
+        let face = FACE_LAYERS.iter().position(|l| *l == m.layer);
+        let direction = DIRECTIONS.iter().position(|d| *d == m.direction);
+
+        match (face, direction) {
+            (Some(face), Some(direction)) => {
+                self.permute(&MOVED_FACELETS[face * 3 + direction])
+            }
+            _ => self.turn(m.layer, m.direction.quarter_turns()),
+        }
+
+        self
+    }
+
+    /// Applies each [`Move`] of a sequence to the facelets in order.
+    pub fn apply_sequence(&mut self, moves: &[Move]) -> &mut Self {
+        for m in moves {
+            self.apply(*m);
+        }
+
+        self
+    }
+
+    /// Returns true if every face is a single color.
+    pub fn is_solved(&self) -> bool {
+        self.0
+            .chunks(9)
+            .all(|face| face.iter().all(|c| *c == face[0]))
+    }
+
+    /// Turns a layer a number of clockwise quarter turns. Wide layers and
+    /// rotations are turned as the single layers which make them up.
+    fn turn(&mut self, layer: Layer, quarter_turns: u8) {
+        for _ in 0..quarter_turns {
+            match layer {
+                Layer::U => self.permute(&MOVED_FACELETS[0]),
+                Layer::D => self.permute(&MOVED_FACELETS[3]),
+                Layer::L => self.permute(&MOVED_FACELETS[6]),
+                Layer::R => self.permute(&MOVED_FACELETS[9]),
+                Layer::F => self.permute(&MOVED_FACELETS[12]),
+                Layer::B => self.permute(&MOVED_FACELETS[15]),
+                Layer::M => self.permute(&SLICE_MOVED_FACELETS[0]),
+                Layer::E => self.permute(&SLICE_MOVED_FACELETS[1]),
+                Layer::S => self.permute(&SLICE_MOVED_FACELETS[2]),
+                Layer::Uw => {
+                    self.turn(Layer::U, 1);
+                    self.turn(Layer::E, 3);
+                }
+                Layer::Dw => {
+                    self.turn(Layer::D, 1);
+                    self.turn(Layer::E, 1);
+                }
+                Layer::Lw => {
+                    self.turn(Layer::L, 1);
+                    self.turn(Layer::M, 1);
+                }
+                Layer::Rw => {
+                    self.turn(Layer::R, 1);
+                    self.turn(Layer::M, 3);
+                }
+                Layer::Fw => {
+                    self.turn(Layer::F, 1);
+                    self.turn(Layer::S, 1);
+                }
+                Layer::Bw => {
+                    self.turn(Layer::B, 1);
+                    self.turn(Layer::S, 3);
+                }
+                Layer::X => {
+                    self.turn(Layer::Rw, 1);
+                    self.turn(Layer::L, 3);
+                }
+                Layer::Y => {
+                    self.turn(Layer::Uw, 1);
+                    self.turn(Layer::D, 3);
+                }
+                Layer::Z => {
+                    self.turn(Layer::Fw, 1);
+                    self.turn(Layer::B, 3);
+                }
+            }
+        }
+    }
+
+    /// Moves the facelets a permutation changes to their new indices,
+    /// leaving the rest untouched.
+    fn permute(&mut self, moved: &MovedFacelets) {
+        let mut colors = [Color::Uninit; MAX_MOVED];
+
+        for (color, (_, from)) in colors.iter_mut().zip(moved.iter()) {
+            *color = self.0[*from];
+        }
+        for (color, (to, _)) in colors.iter().zip(moved.iter()) {
+            self.0[*to] = *color;
+        }
+    }
+}
+
+impl From<&Cube> for Facelets {
+    fn from(cube: &Cube) -> Self {
+        Self(cube.facelets())
+    }
+}
+
+impl From<&Facelets> for Cube {
+    fn from(facelets: &Facelets) -> Self {
+        let mut cube = Cube::solved();
+        cube.set_facelets(&facelets.0);
+
+        cube
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn matches_cube() {
+        let moves = Move::parse_sequence(
+            "R U2 F' L D' B2 M E' S2 x y' z2 Rw Uw' Fw2 Lw Dw2 Bw' R' D",
+        )
+        .unwrap();
+
+        let mut cube = Cube::solved();
+        let mut facelets = Facelets::solved();
+
+        for m in moves {
+            cube.apply(m);
+            facelets.apply(m);
+
+            assert_eq!(facelets, Facelets::from(&cube), "{}", m);
+        }
+
+        assert_eq!(Cube::from(&facelets).facelets(), cube.facelets());
+    }
+
+    #[test]
+    fn moved_facelets_match_permutations() {
+        let slices = [Layer::M, Layer::E, Layer::S];
+        let permutations = MOVE_PERMUTATIONS
+            .iter()
+            .copied()
+            .zip(MOVED_FACELETS.iter())
+            .chain(
+                slices
+                    .iter()
+                    .map(|l| facelet_permutation(*l))
+                    .zip(SLICE_MOVED_FACELETS.iter()),
+            );
+
+        for (permutation, moved) in permutations {
+            let changed = (0..54).filter(|i| permutation[*i] != *i).count();
+            let padding = MAX_MOVED - changed;
+
+            assert!(moved[..changed].iter().all(|(to, from)| to != from));
+            assert!(moved[changed..].iter().all(|pair| *pair == (0, 0)));
+            assert!(padding == 0 || permutation[0] == 0);
+
+            for (to, from) in moved.iter() {
+                assert_eq!(permutation[*to], *from);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_cube_after_random_moves() {
+        let mut rng = StdRng::seed_from_u64(648);
+        let mut cube = Cube::solved();
+        let mut facelets = Facelets::solved();
+
+        for _ in 0..10_000 {
+            let m = cube.apply_random_move(&mut rng);
+            facelets.apply(m);
+        }
+
+        assert_eq!(facelets, Facelets::from(&cube));
+        assert_eq!(Cube::from(&facelets).facelets(), cube.facelets());
+    }
+
+    #[test]
+    fn solved_state() {
+        let mut facelets = Facelets::solved();
+        assert!(facelets.is_solved());
+
+        facelets.apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+        assert!(!facelets.is_solved());
+
+        for _ in 0..5 {
+            facelets
+                .apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+        }
+        assert!(facelets.is_solved());
+    }
+}
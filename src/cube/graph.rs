@@ -0,0 +1,122 @@
+use crate::cube::cube::Cube;
+use crate::cube::moves::Move;
+use std::collections::HashMap;
+
+/// The edges of a state graph, each going from the index of one state to
+/// another along with the move between them.
+pub type Edges = Vec<(usize, usize, Move)>;
+
+/// Returns the graph of states reachable from a cube within a number of face
+/// moves.
+///
+/// States are numbered in the order they're found by a breadth first search,
+/// so the starting state is 0, and each state appears only once. Every face
+/// move from every state less than `depth` moves away gives an edge, so states
+/// reached in more than one way have more than one edge into them.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, graph::*};
+/// let (states, edges) = state_graph(&Cube::solved(), 1);
+///
+/// assert_eq!(states.len(), 19);
+/// assert_eq!(edges.len(), 18);
+/// ```
+pub fn state_graph(start: &Cube, depth: u8) -> (Vec<Cube>, Edges) {
+    let mut states = vec![start.clone()];
+    let mut indices = HashMap::new();
+    let mut edges = Vec::new();
+    indices.insert(start.key(), 0);
+
+    let mut frontier = 0..1;
+    for _ in 0..depth {
+        let end = states.len();
+
+        for from in frontier {
+            for (m, next) in states[from].successors() {
+                let to = *indices.entry(next.key()).or_insert_with(|| {
+                    states.push(next);
+                    states.len() - 1
+                });
+                edges.push((from, to, m));
+            }
+        }
+
+        frontier = end..states.len();
+    }
+
+    (states, edges)
+}
+
+/// Renders a state graph in the DOT language used by Graphviz, with states
+/// labelled by their index, the solved states doubly circled, and edges
+/// labelled by their move.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, graph::*};
+/// let (states, edges) = state_graph(&Cube::solved(), 1);
+/// let dot = to_dot(&states, &edges);
+///
+/// assert!(dot.starts_with("digraph {"));
+/// assert!(dot.contains("0 -> 1 [label=\"U\"];"));
+/// ```
+pub fn to_dot(states: &[Cube], edges: &[(usize, usize, Move)]) -> String {
+    let mut dot = String::from("digraph {\n");
+
+    for (i, state) in states.iter().enumerate() {
+        let shape = if state.is_solved() {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+
+        dot.push_str(&format!("    {} [shape={}];\n", i, shape));
+    }
+
+    for (from, to, m) in edges {
+        dot.push_str(&format!("    {} -> {} [label=\"{}\"];\n", from, to, m));
+    }
+
+    dot.push('}');
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_sizes() {
+        let (states, edges) = state_graph(&Cube::solved(), 0);
+        assert_eq!(states.len(), 1);
+        assert!(edges.is_empty());
+
+        let (states, edges) = state_graph(&Cube::solved(), 1);
+        assert_eq!(states.len(), 19);
+        assert_eq!(edges.len(), 18);
+        assert!(edges.iter().all(|(from, _, _)| *from == 0));
+
+        // There are 243 states two face moves from solved
+        let (states, edges) = state_graph(&Cube::solved(), 2);
+        assert_eq!(states.len(), 1 + 18 + 243);
+        assert_eq!(edges.len(), 18 + 18 * 18);
+
+        for (from, to, m) in edges {
+            let mut cube = states[from].clone();
+            cube.apply(m);
+
+            assert_eq!(cube.key(), states[to].key());
+        }
+    }
+
+    #[test]
+    fn dot_output() {
+        let (states, edges) = state_graph(&Cube::solved(), 1);
+        let dot = to_dot(&states, &edges);
+
+        assert_eq!(dot.matches("->").count(), 18);
+        assert_eq!(dot.matches("doublecircle").count(), 1);
+        assert!(dot.ends_with('}'));
+    }
+}
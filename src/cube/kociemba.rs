@@ -0,0 +1,627 @@
+use crate::cube::cube::Cube;
+use crate::cube::cubie::Color;
+use crate::cube::moves::{Move, FACE_LAYERS};
+use crate::cube::solver::{redundant, Solver};
+use std::collections::VecDeque;
+
+/// The indices of the moves which keep a cube within the subgroup solved by
+/// phase 2: any turn of the top or bottom face, and half turns of the rest.
+/// Moves are numbered in the order of [`Move::all_basic`].
+const PHASE_2_MOVES: [usize; 10] = [0, 1, 2, 3, 4, 5, 8, 11, 14, 17];
+
+/// The facelets of each corner, numbered URF, UFL, ULB, UBR, DFR, DLF, DBL,
+/// DRB, starting from the sticker on the top or bottom face and going
+/// clockwise.
+const CORNER_FACELETS: [[usize; 3]; 8] = [
+    [8, 9, 20],
+    [6, 18, 38],
+    [0, 36, 47],
+    [2, 45, 11],
+    [29, 26, 15],
+    [27, 44, 24],
+    [33, 53, 42],
+    [35, 17, 51],
+];
+
+/// The facelets of each edge, numbered UR, UF, UL, UB, DR, DF, DL, DB, FR,
+/// FL, BL, BR, starting from the sticker on the top or bottom face, or on the
+/// front or back for the middle layer edges.
+const EDGE_FACELETS: [[usize; 2]; 12] = [
+    [5, 10],
+    [7, 19],
+    [3, 37],
+    [1, 46],
+    [32, 16],
+    [28, 25],
+    [30, 43],
+    [34, 52],
+    [23, 12],
+    [21, 41],
+    [50, 39],
+    [48, 14],
+];
+
+/// A cube described by where each corner and edge is and how it's twisted or
+/// flipped there, rather than by its stickers.
+///
+/// `cp[i]` is the corner in position `i`, and `co[i]` how many times it's
+/// twisted clockwise. Edges are the same, with `eo[i]` being 1 if the edge is
+/// flipped relative to the top, bottom, front and back faces.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct CubieCube {
+    cp: [u8; 8],
+    co: [u8; 8],
+    ep: [u8; 12],
+    eo: [u8; 12],
+}
+
+/// The solved cube.
+const SOLVED: CubieCube = CubieCube {
+    cp: [0, 1, 2, 3, 4, 5, 6, 7],
+    co: [0; 8],
+    ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    eo: [0; 12],
+};
+
+/// Clockwise quarter turns of each face, in the order U, D, L, R, F, B.
+const FACE_TURNS: [CubieCube; 6] = [
+    CubieCube {
+        cp: [3, 0, 1, 2, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [3, 0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 5, 6, 7, 4],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [0, 2, 6, 3, 4, 1, 5, 7],
+        co: [0, 1, 2, 0, 0, 2, 1, 0],
+        ep: [0, 1, 10, 3, 4, 5, 9, 7, 8, 2, 6, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [4, 1, 2, 0, 7, 5, 6, 3],
+        co: [2, 0, 0, 1, 1, 0, 0, 2],
+        ep: [8, 1, 2, 3, 11, 5, 6, 7, 4, 9, 10, 0],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [1, 5, 2, 3, 0, 4, 6, 7],
+        co: [1, 2, 0, 0, 2, 1, 0, 0],
+        ep: [0, 9, 2, 3, 4, 8, 6, 7, 1, 5, 10, 11],
+        eo: [0, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 3, 7, 4, 5, 2, 6],
+        co: [0, 0, 1, 2, 0, 0, 2, 1],
+        ep: [0, 1, 2, 11, 4, 5, 6, 10, 8, 9, 3, 7],
+        eo: [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    },
+];
+
+impl CubieCube {
+    /// Reads the pieces of a cube from its stickers, telling faces apart by
+    /// the colors of their centers. Facelets are numbered as in
+    /// [`Cube::facelets`], so a solved facelet `i` is on face `i / 9`. Returns
+    /// None if a piece doesn't match any piece of a real cube.
+    fn from_facelets(facelets: &[Color; 54]) -> Option<Self> {
+        let mut faces = [0; 54];
+
+        for (face, color) in faces.iter_mut().zip(facelets.iter()) {
+            *face = (0..6).find(|f| facelets[f * 9 + 4] == *color)?;
+        }
+
+        let corner_faces = |corner: &[usize; 3]| {
+            [faces[corner[0]], faces[corner[1]], faces[corner[2]]]
+        };
+        let edge_faces = |edge: &[usize; 2]| [faces[edge[0]], faces[edge[1]]];
+
+        let mut cube = SOLVED;
+
+        for (i, corner) in CORNER_FACELETS.iter().enumerate() {
+            let stickers = corner_faces(corner);
+            let twist = stickers.iter().position(|f| *f == 0 || *f == 3)?;
+
+            let j = CORNER_FACELETS.iter().position(|home| {
+                home[1] / 9 == stickers[(twist + 1) % 3]
+                    && home[2] / 9 == stickers[(twist + 2) % 3]
+            })?;
+
+            cube.cp[i] = j as u8;
+            cube.co[i] = twist as u8;
+        }
+
+        for (i, edge) in EDGE_FACELETS.iter().enumerate() {
+            let stickers = edge_faces(edge);
+
+            let (j, flip) =
+                EDGE_FACELETS.iter().enumerate().find_map(|(j, home)| {
+                    let home = [home[0] / 9, home[1] / 9];
+
+                    if home == stickers {
+                        Some((j, 0))
+                    } else if home == [stickers[1], stickers[0]] {
+                        Some((j, 1))
+                    } else {
+                        None
+                    }
+                })?;
+
+            cube.ep[i] = j as u8;
+            cube.eo[i] = flip;
+        }
+
+        Some(cube)
+    }
+
+    /// Returns the cube after applying the changes of another on top of it.
+    fn multiply(&self, other: &Self) -> Self {
+        let mut result = *self;
+
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            result.cp[i] = self.cp[from];
+            result.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            result.ep[i] = self.ep[from];
+            result.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+
+        result
+    }
+
+    /// The twist of every corner, from 0 to 2186.
+    fn twist(&self) -> usize {
+        self.co[..7].iter().fold(0, |n, co| n * 3 + *co as usize)
+    }
+
+    /// The flip of every edge, from 0 to 2047.
+    fn flip(&self) -> usize {
+        self.eo[..11].iter().fold(0, |n, eo| n * 2 + *eo as usize)
+    }
+
+    /// Which four positions hold the middle layer edges, from 0 to 494.
+    fn slice(&self) -> usize {
+        let mut slice = 0;
+        let mut found = 0;
+
+        for i in 0..12 {
+            if self.ep[11 - i] >= 8 {
+                found += 1;
+                slice += binomial(i, found);
+            }
+        }
+
+        slice
+    }
+
+    /// The permutation of the corners, from 0 to 40319.
+    fn corners(&self) -> usize {
+        permutation_index(&self.cp)
+    }
+
+    /// The permutation of the top and bottom layer edges, from 0 to 40319.
+    /// Only meaningful once every middle layer edge is in the middle layer.
+    fn edges(&self) -> usize {
+        permutation_index(&self.ep[..8])
+    }
+
+    /// The permutation of the middle layer edges, from 0 to 23. Only
+    /// meaningful once every middle layer edge is in the middle layer.
+    fn slice_edges(&self) -> usize {
+        permutation_index(&self.ep[8..])
+    }
+}
+
+/// Returns the number of ways to choose k items from n.
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |c, i| c * (n - i) / (i + 1))
+}
+
+/// Returns the position of a permutation in lexicographic order, so the
+/// identity is 0.
+fn permutation_index(perm: &[u8]) -> usize {
+    (0..perm.len()).fold(0, |index, i| {
+        let smaller = perm[i + 1..].iter().filter(|p| **p < perm[i]).count();
+
+        index * (perm.len() - i) + smaller
+    })
+}
+
+/// Returns each of the basic moves as a [`CubieCube`], in the order of
+/// [`Move::all_basic`].
+fn basic_turns() -> [CubieCube; 18] {
+    let mut turns = [SOLVED; 18];
+
+    for (face, turn) in FACE_TURNS.iter().enumerate() {
+        let double = turn.multiply(turn);
+
+        turns[face * 3] = *turn;
+        turns[face * 3 + 1] = double.multiply(turn);
+        turns[face * 3 + 2] = double;
+    }
+
+    turns
+}
+
+/// Returns a table of where each value of a coordinate goes after each move,
+/// found by turning cubes outwards from solved until every value has been
+/// reached. Moves which aren't given are left as `u16::MAX`.
+fn move_table(
+    size: usize,
+    moves: &[usize],
+    turns: &[CubieCube; 18],
+    coordinate: fn(&CubieCube) -> usize,
+) -> Vec<[u16; 18]> {
+    let mut table = vec![[u16::MAX; 18]; size];
+    let mut seen = vec![false; size];
+    let mut queue = VecDeque::new();
+
+    seen[coordinate(&SOLVED)] = true;
+    queue.push_back(SOLVED);
+
+    while let Some(cube) = queue.pop_front() {
+        let from = coordinate(&cube);
+
+        for m in moves {
+            let next = cube.multiply(&turns[*m]);
+            let to = coordinate(&next);
+            table[from][*m] = to as u16;
+
+            if !seen[to] {
+                seen[to] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    table
+}
+
+/// Returns a table of how many moves it takes to solve each pair of values of
+/// two coordinates, indexed by `a * b.len() + b`, found with a breadth first
+/// search outwards from solved.
+fn pruning_table(a: &[[u16; 18]], b: &[[u16; 18]], moves: &[usize]) -> Vec<u8> {
+    let mut table = vec![u8::MAX; a.len() * b.len()];
+    let mut queue = VecDeque::new();
+
+    table[0] = 0;
+    queue.push_back(0);
+
+    while let Some(index) = queue.pop_front() {
+        let (x, y) = (index / b.len(), index % b.len());
+
+        for m in moves {
+            let next = a[x][*m] as usize * b.len() + b[y][*m] as usize;
+
+            if table[next] == u8::MAX {
+                table[next] = table[index] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    table
+}
+
+/// Solves cubes with Kociemba's two phase algorithm, which finds solutions of
+/// around 20 moves in a fraction of a second, though they usually aren't the
+/// shortest possible.
+///
+/// The first phase searches for moves which orient every corner and edge and
+/// bring the middle layer edges into the middle layer. The cube can then be
+/// solved with only turns of the top and bottom faces and half turns of the
+/// rest, which the second phase searches for. Both phases are
+/// [`OptimalSolver`](crate::cube::solver::OptimalSolver) style searches, cut
+/// short using tables of how far each part of the cube is from solved.
+///
+/// Creating a solver builds its tables, which takes a moment and a few
+/// megabytes, so reuse one solver for many cubes.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, kociemba::*, moves::*, solver::*};
+/// let mut cube = Cube::solved();
+/// cube.apply_sequence(&Move::parse_sequence("R U F' L2 D B'").unwrap());
+///
+/// let solution = TwoPhaseSolver::new(20).solve(&cube).unwrap();
+/// cube.apply_sequence(&solution);
+///
+/// assert!(cube.is_solved());
+/// ```
+pub struct TwoPhaseSolver {
+    pub max_depth: usize,
+    turns: [CubieCube; 18],
+    twist_moves: Vec<[u16; 18]>,
+    flip_moves: Vec<[u16; 18]>,
+    slice_moves: Vec<[u16; 18]>,
+    corner_moves: Vec<[u16; 18]>,
+    edge_moves: Vec<[u16; 18]>,
+    slice_edge_moves: Vec<[u16; 18]>,
+    twist_pruning: Vec<u8>,
+    flip_pruning: Vec<u8>,
+    corner_pruning: Vec<u8>,
+    edge_pruning: Vec<u8>,
+}
+
+/// The state of a search for a solution.
+struct Search {
+    start: CubieCube,
+    max_depth: usize,
+    path: Vec<usize>,
+}
+
+impl TwoPhaseSolver {
+    /// Creates a solver which only returns solutions of up to `max_depth`
+    /// moves, building its tables.
+    pub fn new(max_depth: usize) -> Self {
+        let turns = basic_turns();
+        let all: Vec<usize> = (0..18).collect();
+
+        let twist_moves = move_table(2187, &all, &turns, CubieCube::twist);
+        let flip_moves = move_table(2048, &all, &turns, CubieCube::flip);
+        let slice_moves = move_table(495, &all, &turns, CubieCube::slice);
+        let corner_moves =
+            move_table(40320, &PHASE_2_MOVES, &turns, CubieCube::corners);
+        let edge_moves =
+            move_table(40320, &PHASE_2_MOVES, &turns, CubieCube::edges);
+        let slice_edge_moves =
+            move_table(24, &PHASE_2_MOVES, &turns, CubieCube::slice_edges);
+
+        Self {
+            max_depth,
+            turns,
+            twist_pruning: pruning_table(&twist_moves, &slice_moves, &all),
+            flip_pruning: pruning_table(&flip_moves, &slice_moves, &all),
+            corner_pruning: pruning_table(
+                &corner_moves,
+                &slice_edge_moves,
+                &PHASE_2_MOVES,
+            ),
+            edge_pruning: pruning_table(
+                &edge_moves,
+                &slice_edge_moves,
+                &PHASE_2_MOVES,
+            ),
+            twist_moves,
+            flip_moves,
+            slice_moves,
+            corner_moves,
+            edge_moves,
+            slice_edge_moves,
+        }
+    }
+
+    /// Returns a lower bound on the moves needed to finish phase 1.
+    fn phase_1_distance(
+        &self,
+        twist: usize,
+        flip: usize,
+        slice: usize,
+    ) -> usize {
+        let twist = self.twist_pruning[twist * 495 + slice];
+        let flip = self.flip_pruning[flip * 495 + slice];
+
+        twist.max(flip) as usize
+    }
+
+    /// Returns a lower bound on the moves needed to finish phase 2.
+    fn phase_2_distance(
+        &self,
+        corners: usize,
+        edges: usize,
+        slice: usize,
+    ) -> usize {
+        let corners = self.corner_pruning[corners * 24 + slice];
+        let edges = self.edge_pruning[edges * 24 + slice];
+
+        corners.max(edges) as usize
+    }
+
+    /// Searches every phase 1 sequence of a given length, and every phase 2
+    /// sequence short enough to finish within the maximum depth after each
+    /// one found. Returns true once a solution is left in the path.
+    fn phase_1(
+        &self,
+        search: &mut Search,
+        (twist, flip, slice): (usize, usize, usize),
+        depth: usize,
+    ) -> bool {
+        if self.phase_1_distance(twist, flip, slice) > depth {
+            return false;
+        }
+
+        let last = search.path.last().copied();
+
+        if depth == 0 {
+            // Ending with a phase 2 move means phase 1 was already finished
+            // by a shorter sequence, which has been searched
+            if last.map_or(false, |m| PHASE_2_MOVES.contains(&m)) {
+                return false;
+            }
+
+            let cube = search
+                .path
+                .iter()
+                .fold(search.start, |cube, m| cube.multiply(&self.turns[*m]));
+            let coordinates =
+                (cube.corners(), cube.edges(), cube.slice_edges());
+
+            return (0..=search.max_depth - search.path.len())
+                .any(|depth| self.phase_2(search, coordinates, depth));
+        }
+
+        for m in 0..18 {
+            if last.map_or(false, |last| skip(last, m)) {
+                continue;
+            }
+
+            search.path.push(m);
+
+            let next = (
+                self.twist_moves[twist][m] as usize,
+                self.flip_moves[flip][m] as usize,
+                self.slice_moves[slice][m] as usize,
+            );
+            if self.phase_1(search, next, depth - 1) {
+                return true;
+            }
+
+            search.path.pop();
+        }
+
+        false
+    }
+
+    /// Searches every phase 2 sequence of a given length for one which
+    /// solves the cube. Returns true once a solution is left in the path.
+    fn phase_2(
+        &self,
+        search: &mut Search,
+        (corners, edges, slice): (usize, usize, usize),
+        depth: usize,
+    ) -> bool {
+        let distance = self.phase_2_distance(corners, edges, slice);
+
+        if distance > depth {
+            return false;
+        }
+        if depth == 0 {
+            return true;
+        }
+
+        let last = search.path.last().copied();
+
+        for m in PHASE_2_MOVES.iter().copied() {
+            if last.map_or(false, |last| skip(last, m)) {
+                continue;
+            }
+
+            search.path.push(m);
+
+            let next = (
+                self.corner_moves[corners][m] as usize,
+                self.edge_moves[edges][m] as usize,
+                self.slice_edge_moves[slice][m] as usize,
+            );
+            if self.phase_2(search, next, depth - 1) {
+                return true;
+            }
+
+            search.path.pop();
+        }
+
+        false
+    }
+}
+
+impl Solver for TwoPhaseSolver {
+    /// Returns the first solution found of at most `max_depth` moves, or None
+    /// if there isn't one or the cube can't be solved.
+    fn solve(&self, cube: &Cube) -> Option<Vec<Move>> {
+        if !cube.is_solvable() {
+            return None;
+        }
+
+        let start = CubieCube::from_facelets(&cube.facelets())?;
+        let mut search = Search {
+            start,
+            max_depth: self.max_depth,
+            path: Vec::new(),
+        };
+        let coordinates = (start.twist(), start.flip(), start.slice());
+
+        if (0..=self.max_depth)
+            .any(|depth| self.phase_1(&mut search, coordinates, depth))
+        {
+            let moves: Vec<Move> = Move::all_basic().collect();
+
+            Some(search.path.iter().map(|m| moves[*m]).collect())
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns true if a move can be skipped straight after another, as in
+/// [`redundant`].
+fn skip(last: usize, next: usize) -> bool {
+    redundant(FACE_LAYERS[last / 3], FACE_LAYERS[next / 3])
+}
+
+/// Solves a cube with a [`TwoPhaseSolver`], returning a solution of at most
+/// `max_depth` moves if one is found.
+///
+/// This builds the solver's tables on every call, so to solve many cubes
+/// create one [`TwoPhaseSolver`] and reuse it instead.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, kociemba::*, moves::*};
+/// let mut cube = Cube::solved();
+/// cube.apply_sequence(&Move::parse_sequence("R U R' U'").unwrap());
+///
+/// let solution = solve_kociemba(&cube, 20).unwrap();
+/// assert!(solution.len() <= 20);
+/// ```
+pub fn solve_kociemba(cube: &Cube, max_depth: usize) -> Option<Vec<Move>> {
+    TwoPhaseSolver::new(max_depth).solve(cube)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn turns_match_cube() {
+        for (m, turn) in Move::all_basic().zip(basic_turns().iter()) {
+            let mut cube = Cube::solved();
+            cube.apply(m);
+
+            assert_eq!(
+                CubieCube::from_facelets(&cube.facelets()),
+                Some(SOLVED.multiply(turn))
+            );
+        }
+    }
+
+    #[test]
+    fn solves_scrambles() {
+        let mut rng = StdRng::seed_from_u64(710);
+        let solver = TwoPhaseSolver::new(24);
+
+        for _ in 0..20 {
+            let mut cube = Cube::solved();
+            cube.scramble(30, &mut rng);
+
+            let solution = solver.solve(&cube).unwrap();
+            assert!(solution.len() <= 24);
+
+            cube.apply_sequence(&solution);
+            assert!(cube.is_solved());
+        }
+    }
+
+    #[test]
+    fn respects_max_depth() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+
+        assert_eq!(solve_kociemba(&cube, 2), None);
+
+        let solution = solve_kociemba(&cube, 3).unwrap();
+        assert_eq!(solution, Move::parse_sequence("F' U' R'").unwrap());
+    }
+}
@@ -1,4 +1,19 @@
 #[macro_use]
 pub mod macros;
+pub mod algorithms;
+pub mod cached;
 pub mod cube;
 pub mod cubie;
+pub mod facelets;
+pub mod graph;
+#[cfg(feature = "kociemba")]
+pub mod kociemba;
+pub mod moves;
+pub mod pocket;
+pub mod puzzle;
+pub mod scramble;
+pub mod solver;
+pub mod supercube;
+pub mod symmetry;
+pub mod tracked;
+pub mod zobrist;
@@ -0,0 +1,906 @@
+use std::fmt;
+use std::ops::Mul;
+use std::str::FromStr;
+
+/// A turnable layer of a 3x3 cube, named using standard cube notation.
+///
+/// Besides the six outer faces this includes the six wide layers (an outer
+/// face together with the slice next to it, written `Rw` or `r`), the three
+/// middle slices (`M` follows `L`, `E` follows `D`, and `S` follows `F`) and
+/// the three whole cube rotations (`X` follows `R`, `Y` follows `U`, and `Z`
+/// follows `F`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    U,
+    D,
+    L,
+    R,
+    F,
+    B,
+    Uw,
+    Dw,
+    Lw,
+    Rw,
+    Fw,
+    Bw,
+    M,
+    E,
+    S,
+    X,
+    Y,
+    Z,
+}
+
+/// The six outer face layers, which make up the basic moves of the cube.
+pub(crate) const FACE_LAYERS: [Layer; 6] =
+    [Layer::U, Layer::D, Layer::L, Layer::R, Layer::F, Layer::B];
+
+/// Every direction a layer can be turned in.
+pub(crate) const DIRECTIONS: [Direction; 3] = [
+    Direction::Clockwise,
+    Direction::CounterClockwise,
+    Direction::Double,
+];
+
+impl Layer {
+    /// Returns the layer on the opposite side of the cube for the six outer
+    /// face layers, or None for slices and rotations.
+    pub const fn opposite(&self) -> Option<Self> {
+        match self {
+            Layer::U => Some(Layer::D),
+            Layer::D => Some(Layer::U),
+            Layer::L => Some(Layer::R),
+            Layer::R => Some(Layer::L),
+            Layer::F => Some(Layer::B),
+            Layer::B => Some(Layer::F),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the layer is a whole cube rotation rather than a turn.
+    pub const fn is_rotation(&self) -> bool {
+        matches!(self, Layer::X | Layer::Y | Layer::Z)
+    }
+
+    /// Returns true if the layer is an outer face turned together with the
+    /// slice next to it.
+    pub const fn is_wide(&self) -> bool {
+        matches!(
+            self,
+            Layer::Uw
+                | Layer::Dw
+                | Layer::Lw
+                | Layer::Rw
+                | Layer::Fw
+                | Layer::Bw
+        )
+    }
+
+    /// Returns the text used for the layer in standard notation.
+    pub const fn notation(&self) -> &'static str {
+        match self {
+            Layer::U => "U",
+            Layer::D => "D",
+            Layer::L => "L",
+            Layer::R => "R",
+            Layer::F => "F",
+            Layer::B => "B",
+            Layer::Uw => "Uw",
+            Layer::Dw => "Dw",
+            Layer::Lw => "Lw",
+            Layer::Rw => "Rw",
+            Layer::Fw => "Fw",
+            Layer::Bw => "Bw",
+            Layer::M => "M",
+            Layer::E => "E",
+            Layer::S => "S",
+            Layer::X => "x",
+            Layer::Y => "y",
+            Layer::Z => "z",
+        }
+    }
+
+    /// Returns the layer for some text of standard notation, if there is one.
+    /// Wide layers may be written either as `Rw` or as `r`, and rotations may
+    /// be written in either case.
+    pub fn from_notation(s: &str) -> Option<Self> {
+        match s {
+            "U" => Some(Layer::U),
+            "D" => Some(Layer::D),
+            "L" => Some(Layer::L),
+            "R" => Some(Layer::R),
+            "F" => Some(Layer::F),
+            "B" => Some(Layer::B),
+            "Uw" | "u" => Some(Layer::Uw),
+            "Dw" | "d" => Some(Layer::Dw),
+            "Lw" | "l" => Some(Layer::Lw),
+            "Rw" | "r" => Some(Layer::Rw),
+            "Fw" | "f" => Some(Layer::Fw),
+            "Bw" | "b" => Some(Layer::Bw),
+            "M" => Some(Layer::M),
+            "E" => Some(Layer::E),
+            "S" => Some(Layer::S),
+            "x" | "X" => Some(Layer::X),
+            "y" | "Y" => Some(Layer::Y),
+            "z" | "Z" => Some(Layer::Z),
+            _ => None,
+        }
+    }
+}
+
+/// The direction of a turn, as seen when looking straight at the face that the
+/// turned layer follows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Clockwise,
+    CounterClockwise,
+    Double,
+}
+
+impl Direction {
+    /// Returns the number of clockwise quarter turns the direction represents.
+    pub const fn quarter_turns(&self) -> u8 {
+        match self {
+            Direction::Clockwise => 1,
+            Direction::CounterClockwise => 3,
+            Direction::Double => 2,
+        }
+    }
+
+    /// Returns the direction equal to a number of clockwise quarter turns, or
+    /// None if the quarter turns add up to a full rotation.
+    pub const fn from_quarter_turns(turns: u8) -> Option<Self> {
+        match turns % 4 {
+            1 => Some(Direction::Clockwise),
+            2 => Some(Direction::Double),
+            3 => Some(Direction::CounterClockwise),
+            _ => None,
+        }
+    }
+
+    /// Returns the direction which undoes this one.
+    pub const fn inverse(&self) -> Self {
+        match self {
+            Direction::Clockwise => Direction::CounterClockwise,
+            Direction::CounterClockwise => Direction::Clockwise,
+            Direction::Double => Direction::Double,
+        }
+    }
+}
+
+/// A single move in standard cube notation, made up of the [`Layer`] being
+/// turned and the [`Direction`] it is turned in.
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::moves::*;
+/// let m: Move = "R'".parse().unwrap();
+///
+/// assert_eq!(m, Move::R_PRIME);
+/// assert_eq!(m.inverse(), Move::R);
+/// assert_eq!(m.to_string(), "R'");
+/// ```
+///
+/// Moves are ordered by their layer, in the order the [`Layer`] variants are
+/// declared, and then by their direction: clockwise, counter-clockwise, and
+/// then double.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Move {
+    pub layer: Layer,
+    pub direction: Direction,
+}
+
+impl Move {
+    pub const U: Self = Self::new(Layer::U, Direction::Clockwise);
+    pub const U_PRIME: Self = Self::new(Layer::U, Direction::CounterClockwise);
+    pub const U2: Self = Self::new(Layer::U, Direction::Double);
+    pub const D: Self = Self::new(Layer::D, Direction::Clockwise);
+    pub const D_PRIME: Self = Self::new(Layer::D, Direction::CounterClockwise);
+    pub const D2: Self = Self::new(Layer::D, Direction::Double);
+    pub const L: Self = Self::new(Layer::L, Direction::Clockwise);
+    pub const L_PRIME: Self = Self::new(Layer::L, Direction::CounterClockwise);
+    pub const L2: Self = Self::new(Layer::L, Direction::Double);
+    pub const R: Self = Self::new(Layer::R, Direction::Clockwise);
+    pub const R_PRIME: Self = Self::new(Layer::R, Direction::CounterClockwise);
+    pub const R2: Self = Self::new(Layer::R, Direction::Double);
+    pub const F: Self = Self::new(Layer::F, Direction::Clockwise);
+    pub const F_PRIME: Self = Self::new(Layer::F, Direction::CounterClockwise);
+    pub const F2: Self = Self::new(Layer::F, Direction::Double);
+    pub const B: Self = Self::new(Layer::B, Direction::Clockwise);
+    pub const B_PRIME: Self = Self::new(Layer::B, Direction::CounterClockwise);
+    pub const B2: Self = Self::new(Layer::B, Direction::Double);
+    pub const M: Self = Self::new(Layer::M, Direction::Clockwise);
+    pub const M_PRIME: Self = Self::new(Layer::M, Direction::CounterClockwise);
+    pub const M2: Self = Self::new(Layer::M, Direction::Double);
+    pub const E: Self = Self::new(Layer::E, Direction::Clockwise);
+    pub const E_PRIME: Self = Self::new(Layer::E, Direction::CounterClockwise);
+    pub const E2: Self = Self::new(Layer::E, Direction::Double);
+    pub const S: Self = Self::new(Layer::S, Direction::Clockwise);
+    pub const S_PRIME: Self = Self::new(Layer::S, Direction::CounterClockwise);
+    pub const S2: Self = Self::new(Layer::S, Direction::Double);
+    pub const X: Self = Self::new(Layer::X, Direction::Clockwise);
+    pub const X_PRIME: Self = Self::new(Layer::X, Direction::CounterClockwise);
+    pub const X2: Self = Self::new(Layer::X, Direction::Double);
+    pub const Y: Self = Self::new(Layer::Y, Direction::Clockwise);
+    pub const Y_PRIME: Self = Self::new(Layer::Y, Direction::CounterClockwise);
+    pub const Y2: Self = Self::new(Layer::Y, Direction::Double);
+    pub const Z: Self = Self::new(Layer::Z, Direction::Clockwise);
+    pub const Z_PRIME: Self = Self::new(Layer::Z, Direction::CounterClockwise);
+    pub const Z2: Self = Self::new(Layer::Z, Direction::Double);
+
+    pub const fn new(layer: Layer, direction: Direction) -> Self {
+        Self {
+            layer,
+            direction,
+        }
+    }
+
+    /// Returns the move which undoes this one.
+    pub const fn inverse(&self) -> Self {
+        Self::new(self.layer, self.direction.inverse())
+    }
+
+    /// Returns the move which turns a range of layers of a 3x3x3 in the
+    /// direction of a face, where layers are counted inwards from that face
+    /// starting at 1, as in the notation `2-3Rw`. Returns None if the face
+    /// isn't an outer face, or the range is empty or deeper than the cube.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::moves::*;
+    /// let turn = |first, last| {
+    ///     Move::from_layer_range(Layer::R, first, last, Direction::Clockwise)
+    /// };
+    ///
+    /// assert_eq!(turn(1, 1), Some(Move::R));
+    /// assert_eq!(turn(2, 2), Some(Move::M_PRIME));
+    /// assert_eq!(turn(1, 3), Some(Move::X));
+    /// assert_eq!(turn(2, 4), None);
+    /// ```
+    pub fn from_layer_range(
+        face: Layer,
+        first: usize,
+        last: usize,
+        direction: Direction,
+    ) -> Option<Self> {
+        let opposite = face.opposite()?;
+        let wide = |face| match face {
+            Layer::U => Layer::Uw,
+            Layer::D => Layer::Dw,
+            Layer::L => Layer::Lw,
+            Layer::R => Layer::Rw,
+            Layer::F => Layer::Fw,
+            _ => Layer::Bw,
+        };
+        // Slices follow the L, D and F faces, and rotations follow the R, U
+        // and F faces
+        let (slice, slice_face, rotation, rotation_face) = match face {
+            Layer::U | Layer::D => (Layer::E, Layer::D, Layer::Y, Layer::U),
+            Layer::L | Layer::R => (Layer::M, Layer::L, Layer::X, Layer::R),
+            _ => (Layer::S, Layer::F, Layer::Z, Layer::F),
+        };
+
+        let (layer, reversed) = match (first, last) {
+            (1, 1) => (face, false),
+            (2, 2) => (slice, face != slice_face),
+            (3, 3) => (opposite, true),
+            (1, 2) => (wide(face), false),
+            (2, 3) => (wide(opposite), true),
+            (1, 3) => (rotation, face != rotation_face),
+            _ => return None,
+        };
+
+        Some(if reversed {
+            Self::new(layer, direction.inverse())
+        } else {
+            Self::new(layer, direction)
+        })
+    }
+
+    /// Returns the 18 basic moves: every outer face turned in every direction.
+    /// Moves are yielded face by face, in the order U, D, L, R, F, B, and each
+    /// face is turned clockwise, counter-clockwise, and then twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::moves::*;
+    /// let mut moves = Move::all_basic();
+    ///
+    /// assert_eq!(moves.next(), Some(Move::U));
+    /// assert_eq!(moves.next(), Some(Move::U_PRIME));
+    /// assert_eq!(moves.next(), Some(Move::U2));
+    /// assert_eq!(moves.next(), Some(Move::D));
+    /// ```
+    pub fn all_basic() -> impl Iterator<Item = Self> {
+        FACE_LAYERS.iter().flat_map(|layer| {
+            DIRECTIONS
+                .iter()
+                .map(move |direction| Self::new(*layer, *direction))
+        })
+    }
+
+    /// Parses a whitespace separated sequence of moves such as `"R U R' U'"`.
+    ///
+    /// Sequences copied from timers and algorithm sites can be parsed as they
+    /// are: rotations and wide moves may appear anywhere in the sequence, any
+    /// amount of whitespace or line breaks may separate moves, and anything
+    /// after `//` on a line is ignored as a comment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::moves::*;
+    /// let moves = Move::parse_sequence("R U2 x'").unwrap();
+    ///
+    /// assert_eq!(moves, vec![Move::R, Move::U2, Move::X_PRIME]);
+    ///
+    /// let moves = Move::parse_sequence("y2 Rw U' // setup\nr2").unwrap();
+    ///
+    /// assert_eq!(moves.len(), 4);
+    /// assert_eq!(moves[3], Move::new(Layer::Rw, Direction::Double));
+    /// ```
+    pub fn parse_sequence(s: &str) -> Result<Vec<Self>, ParseMoveError> {
+        s.lines()
+            .map(|line| line.split("//").next().unwrap_or(""))
+            .flat_map(str::split_whitespace)
+            .map(str::parse)
+            .collect()
+    }
+
+    /// Parses a separate sequence of moves from each line of a string, as
+    /// when reading a file of scrambles, pairing each with its line number
+    /// counted from 1. Lines which are blank or only hold a comment are
+    /// skipped, so a line which fails to parse can be reported by number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::moves::*;
+    /// let lines = Move::parse_lines("R U\n\n// comment\nF Q");
+    ///
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0], (1, Ok(vec![Move::R, Move::U])));
+    /// assert_eq!(lines[1].0, 4);
+    /// assert!(lines[1].1.is_err());
+    /// ```
+    pub fn parse_lines(
+        s: &str,
+    ) -> Vec<(usize, Result<Vec<Self>, ParseMoveError>)> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                !line.split("//").next().unwrap_or("").trim().is_empty()
+            })
+            .map(|(i, line)| (i + 1, Self::parse_sequence(line)))
+            .collect()
+    }
+
+    /// Returns the move's notation written with a Unicode prime (`′`) for
+    /// counter-clockwise turns and a superscript two (`²`) for double turns,
+    /// as some interfaces prefer. Both forms are accepted when parsing moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustytwisty::cube::moves::*;
+    /// assert_eq!(Move::R_PRIME.to_string_unicode(), "R\u{2032}");
+    /// assert_eq!(Move::U2.to_string_unicode(), "U\u{b2}");
+    /// assert_eq!("U\u{b2}".parse(), Ok(Move::U2));
+    /// ```
+    pub fn to_string_unicode(&self) -> String {
+        let modifier = match self.direction {
+            Direction::Clockwise => "",
+            Direction::CounterClockwise => "\u{2032}",
+            Direction::Double => "\u{b2}",
+        };
+
+        format!("{}{}", self.layer.notation(), modifier)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let modifier = match self.direction {
+            Direction::Clockwise => "",
+            Direction::CounterClockwise => "'",
+            Direction::Double => "2",
+        };
+
+        write!(f, "{}{}", self.layer.notation(), modifier)
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseMoveError::Empty);
+        }
+
+        let prefix = s
+            .find(|c: char| !c.is_ascii_digit() && c != '-')
+            .unwrap_or_else(|| s.len());
+        if prefix > 0 {
+            return parse_layer_range(s, prefix);
+        }
+
+        let split = s
+            .find(|c| ['\'', '2', '\u{2032}', '\u{b2}'].contains(&c))
+            .unwrap_or_else(|| s.len());
+        let (layer, modifier) = s.split_at(split);
+
+        let layer = match Layer::from_notation(layer) {
+            Some(layer) => layer,
+            None => return Err(ParseMoveError::InvalidMove(s.to_string())),
+        };
+
+        let direction = match modifier {
+            "" => Direction::Clockwise,
+            "'" | "\u{2032}" => Direction::CounterClockwise,
+            "2" | "2'" | "\u{b2}" | "\u{b2}\u{2032}" => Direction::Double,
+            _ => return Err(ParseMoveError::InvalidMove(s.to_string())),
+        };
+
+        Ok(Self::new(layer, direction))
+    }
+}
+
+/// Parses a move prefixed with the layers it turns, counted inwards from its
+/// face: `2R` turns the second layer alone, `3Rw` the outer three layers, and
+/// `2-3Rw` the second and third layers.
+fn parse_layer_range(s: &str, prefix: usize) -> Result<Move, ParseMoveError> {
+    let invalid = || ParseMoveError::InvalidMove(s.to_string());
+    let (range, turn) = s.split_at(prefix);
+    let turn: Move = turn.parse().map_err(|_| invalid())?;
+
+    let (face, wide) = match turn.layer {
+        Layer::Uw => (Layer::U, true),
+        Layer::Dw => (Layer::D, true),
+        Layer::Lw => (Layer::L, true),
+        Layer::Rw => (Layer::R, true),
+        Layer::Fw => (Layer::F, true),
+        Layer::Bw => (Layer::B, true),
+        layer if FACE_LAYERS.contains(&layer) => (layer, false),
+        _ => return Err(invalid()),
+    };
+
+    let mut bounds = range.splitn(2, '-').map(str::parse::<usize>);
+    let (first, last) = match (bounds.next(), bounds.next()) {
+        (Some(Ok(first)), Some(Ok(last))) => (first, last),
+        (Some(Ok(last)), None) if wide => (1, last),
+        (Some(Ok(layer)), None) => (layer, layer),
+        _ => return Err(invalid()),
+    };
+
+    Move::from_layer_range(face, first, last, turn.direction)
+        .ok_or_else(|| ParseMoveError::LayerOutOfRange(s.to_string()))
+}
+
+/// Returns an equivalent sequence of moves with consecutive turns of the same
+/// layer merged together, and any turns which cancel out removed.
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::moves::*;
+/// let moves = Move::parse_sequence("R U U' R F2 F2").unwrap();
+///
+/// assert_eq!(optimize(&moves), vec![Move::R2]);
+/// ```
+pub fn optimize(moves: &[Move]) -> Vec<Move> {
+    let mut optimized: Vec<Move> = Vec::with_capacity(moves.len());
+
+    for m in moves {
+        push_merged(&mut optimized, *m);
+    }
+
+    optimized
+}
+
+/// Pushes a move onto the end of a sequence, merging it with the last move if
+/// they turn the same layer.
+fn push_merged(moves: &mut Vec<Move>, m: Move) {
+    match moves.last() {
+        Some(last) if last.layer == m.layer => {
+            let turns =
+                last.direction.quarter_turns() + m.direction.quarter_turns();
+
+            moves.pop();
+            if let Some(direction) = Direction::from_quarter_turns(turns) {
+                moves.push(Move::new(m.layer, direction));
+            }
+        }
+        _ => moves.push(m),
+    }
+}
+
+/// Returns an equivalent sequence of moves in a canonical form, so that
+/// sequences which only differ in the order of turns of opposite faces compare
+/// equal. As well as merging and cancelling turns like [`optimize`], turns of
+/// opposite faces next to each other are merged with any other turns of the
+/// same two faces around them, and put in the same order as the faces of
+/// [`Move::all_basic`].
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::moves::*;
+/// let a = Move::parse_sequence("R L U").unwrap();
+/// let b = Move::parse_sequence("L R U").unwrap();
+///
+/// assert_eq!(canonicalize_sequence(&a), canonicalize_sequence(&b));
+/// ```
+pub fn canonicalize_sequence(moves: &[Move]) -> Vec<Move> {
+    let mut canonical: Vec<Move> = Vec::with_capacity(moves.len());
+
+    for m in moves {
+        let opposite = match m.layer.opposite() {
+            Some(opposite) => opposite,
+            None => {
+                push_merged(&mut canonical, *m);
+                continue;
+            }
+        };
+
+        // The turns of this axis at the end of the sequence so far, which
+        // are already merged, so there are at most two of them
+        let run = canonical
+            .iter()
+            .rev()
+            .take_while(|l| l.layer == m.layer || l.layer == opposite)
+            .count();
+        let mut axis = canonical.split_off(canonical.len() - run);
+        axis.push(*m);
+
+        let order = |layer| FACE_LAYERS.iter().position(|l| *l == layer);
+        let mut faces = [m.layer, opposite];
+        faces.sort_by_key(|layer| order(*layer));
+
+        for face in faces.iter() {
+            let turns: u8 = axis
+                .iter()
+                .filter(|l| l.layer == *face)
+                .map(|l| l.direction.quarter_turns())
+                .sum();
+
+            if let Some(direction) = Direction::from_quarter_turns(turns) {
+                canonical.push(Move::new(*face, direction));
+            }
+        }
+    }
+
+    canonical
+}
+
+/// Formats a sequence of moves in standard notation, separated by spaces and
+/// wrapped onto a new line after every `per_line` moves. A `per_line` of 0
+/// keeps every move on one line.
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::moves::*;
+/// let moves = Move::parse_sequence("R U R' U' R' F R F'").unwrap();
+///
+/// assert_eq!(format_sequence(&moves, 4), "R U R' U'\nR' F R F'");
+/// ```
+pub fn format_sequence(moves: &[Move], per_line: usize) -> String {
+    let per_line = if per_line == 0 {
+        moves.len().max(1)
+    } else {
+        per_line
+    };
+
+    moves
+        .chunks(per_line)
+        .map(|line| {
+            line.iter()
+                .map(Move::to_string)
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A sequence of moves, such as an algorithm or a scramble.
+///
+/// Sequences compose by multiplication, which applies the left hand sequence
+/// followed by the right hand one. They can be parsed from and displayed in
+/// standard notation, with the moves separated by spaces.
+///
+/// # Examples
+///
+/// ```
+/// # use rustytwisty::cube::moves::*;
+/// let sune = Sequence(Move::parse_sequence("R U R' U R U2 R'").unwrap());
+///
+/// let alg = sune.clone() * sune.inverse();
+///
+/// assert_eq!(alg.0.len(), 14);
+/// assert!(alg.optimize().0.is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sequence(pub Vec<Move>);
+
+impl Sequence {
+    /// Returns the sequence which undoes this one.
+    pub fn inverse(&self) -> Self {
+        Self(self.0.iter().rev().map(Move::inverse).collect())
+    }
+
+    /// Returns the sequence with redundant moves merged or removed, as done by
+    /// [`optimize`].
+    pub fn optimize(&self) -> Self {
+        Self(optimize(&self.0))
+    }
+}
+
+impl From<Vec<Move>> for Sequence {
+    fn from(moves: Vec<Move>) -> Self {
+        Self(moves)
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format_sequence(&self.0, 0))
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Move::parse_sequence(s).map(Self)
+    }
+}
+
+impl Mul for Sequence {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+/// An error returned when a string can't be parsed as a [`Move`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseMoveError {
+    Empty,
+    InvalidMove(String),
+    /// The move turns layers which a 3x3x3 doesn't have, such as `4R`.
+    LayerOutOfRange(String),
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoveError::Empty => write!(f, "empty move"),
+            ParseMoveError::InvalidMove(s) => write!(f, "invalid move `{}`", s),
+            ParseMoveError::LayerOutOfRange(s) => {
+                write!(f, "move `{}` turns layers deeper than the cube", s)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_round_trip() {
+        for s in &[
+            "U", "D'", "L2", "R", "F'", "B2", "M", "E'", "S2", "x", "y'", "Rw",
+            "Uw2", "Fw'",
+        ] {
+            let m: Move = s.parse().unwrap();
+
+            assert_eq!(&m.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn move_parse_errors() {
+        assert_eq!("".parse::<Move>(), Err(ParseMoveError::Empty));
+        assert_eq!(
+            "Q".parse::<Move>(),
+            Err(ParseMoveError::InvalidMove("Q".to_string()))
+        );
+        assert_eq!(
+            "R3".parse::<Move>(),
+            Err(ParseMoveError::InvalidMove("R3".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_mixed_sequence() {
+        let moves = Move::parse_sequence(
+            "  y2 R U'  Rw2 F' x' // inspection\n\tu2 r' D2 B z  ",
+        )
+        .unwrap();
+
+        assert_eq!(moves, vec![
+            Move::Y2,
+            Move::R,
+            Move::U_PRIME,
+            Move::new(Layer::Rw, Direction::Double),
+            Move::F_PRIME,
+            Move::X_PRIME,
+            Move::new(Layer::Uw, Direction::Double),
+            Move::new(Layer::Rw, Direction::CounterClockwise),
+            Move::D2,
+            Move::B,
+            Move::Z,
+        ]);
+    }
+
+    #[test]
+    fn parse_numbered_lines() {
+        let lines = Move::parse_lines("R U R'\n   \nx y // rotate\n\nR Q U\n");
+
+        assert_eq!(lines, vec![
+            (1, Ok(vec![Move::R, Move::U, Move::R_PRIME])),
+            (3, Ok(vec![Move::X, Move::Y])),
+            (5, Err(ParseMoveError::InvalidMove("Q".to_string()))),
+        ]);
+    }
+
+    #[test]
+    fn layer_prefixed_moves() {
+        let parse = |s: &str| s.parse::<Move>();
+
+        assert_eq!(parse("1R"), Ok(Move::R));
+        assert_eq!(parse("2R"), Ok(Move::M_PRIME));
+        assert_eq!(parse("2L'"), Ok(Move::M_PRIME));
+        assert_eq!(parse("2U2"), Ok(Move::E2));
+        assert_eq!(parse("2F"), Ok(Move::S));
+        assert_eq!(parse("3R"), Ok(Move::L_PRIME));
+        assert_eq!(
+            parse("2Rw"),
+            Ok(Move::new(Layer::Rw, Direction::Clockwise))
+        );
+        assert_eq!(
+            parse("2-3Rw"),
+            Ok(Move::new(Layer::Lw, Direction::CounterClockwise))
+        );
+        assert_eq!(parse("3Rw'"), Ok(Move::X_PRIME));
+        assert_eq!(parse("1-3Dw"), Ok(Move::Y_PRIME));
+
+        assert_eq!(
+            parse("4R"),
+            Err(ParseMoveError::LayerOutOfRange("4R".to_string()))
+        );
+        assert_eq!(
+            parse("3-2Rw"),
+            Err(ParseMoveError::LayerOutOfRange("3-2Rw".to_string()))
+        );
+        assert_eq!(
+            parse("0R"),
+            Err(ParseMoveError::LayerOutOfRange("0R".to_string()))
+        );
+        assert_eq!(
+            parse("2M"),
+            Err(ParseMoveError::InvalidMove("2M".to_string()))
+        );
+        assert_eq!(
+            parse("2-R"),
+            Err(ParseMoveError::InvalidMove("2-R".to_string()))
+        );
+        assert_eq!(
+            parse("2"),
+            Err(ParseMoveError::InvalidMove("2".to_string()))
+        );
+    }
+
+    #[test]
+    fn all_basic_moves() {
+        let moves: Vec<Move> = Move::all_basic().collect();
+        assert_eq!(moves.len(), 18);
+
+        let mut distinct = moves.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 18);
+
+        assert!(moves.iter().all(|m| FACE_LAYERS.contains(&m.layer)));
+    }
+
+    #[test]
+    fn canonical_sequences() {
+        let canonical =
+            |s| canonicalize_sequence(&Move::parse_sequence(s).unwrap());
+
+        assert_eq!(canonical("L R"), canonical("R L"));
+        assert_eq!(canonical("R L"), vec![Move::L, Move::R]);
+        assert_eq!(canonical("U R L' R2 U2"), canonical("U L' R' U2"));
+        assert_eq!(canonical("R L R'"), vec![Move::L]);
+        assert_eq!(canonical("R U U' L R'"), vec![Move::L]);
+        assert_eq!(canonical("F B F' B'"), vec![]);
+        assert_eq!(canonical("R M M' x x'"), vec![Move::R]);
+        assert_ne!(canonical("R U"), canonical("U R"));
+    }
+
+    #[test]
+    fn format_sequence_wraps() {
+        let moves =
+            Move::parse_sequence("R U R' U' R' F R2 U' R' U' R U").unwrap();
+        let formatted = format_sequence(&moves, 4);
+
+        assert_eq!(formatted.lines().count(), 3);
+        assert_eq!(formatted.lines().nth(1), Some("R' F R2 U'"));
+
+        assert_eq!(format_sequence(&moves, 0).lines().count(), 1);
+        assert_eq!(format_sequence(&moves[..5], 4).lines().count(), 2);
+        assert_eq!(format_sequence(&[], 4), "");
+    }
+
+    #[test]
+    fn move_inverse() {
+        assert_eq!(Move::R.inverse(), Move::R_PRIME);
+        assert_eq!(Move::R_PRIME.inverse(), Move::R);
+        assert_eq!(Move::R2.inverse(), Move::R2);
+    }
+
+    #[test]
+    fn move_ordering() {
+        let mut moves = Move::parse_sequence("U2 R' D R2 U R x").unwrap();
+        moves.sort();
+
+        assert_eq!(moves, Move::parse_sequence("U U2 D R R' R2 x").unwrap());
+    }
+
+    #[test]
+    fn optimize_cancels() {
+        let moves = Move::parse_sequence("R U R' U' U R U' R'").unwrap();
+
+        assert_eq!(optimize(&moves), vec![]);
+
+        let moves = Move::parse_sequence("R R R U2 U").unwrap();
+
+        assert_eq!(optimize(&moves), vec![Move::R_PRIME, Move::U_PRIME]);
+    }
+
+    #[test]
+    fn sequence_mul_inverse() {
+        let seq = Sequence(Move::parse_sequence("R U2 F' L D B2").unwrap());
+
+        assert_eq!((seq.clone() * seq.inverse()).optimize(), Sequence(vec![]));
+        assert_eq!(seq.inverse().inverse(), seq);
+    }
+
+    #[test]
+    fn sequence_round_trip() {
+        let seq: Sequence = "R U R' U'".parse().unwrap();
+
+        assert_eq!(seq.0.len(), 4);
+        assert_eq!(seq.to_string(), "R U R' U'");
+
+        let seq: Sequence = " x  Rw2\nM' ".parse().unwrap();
+        assert_eq!(seq.to_string(), "x Rw2 M'");
+
+        assert_eq!(Sequence(vec![]).to_string(), "");
+        assert!("R Q".parse::<Sequence>().is_err());
+    }
+
+    #[test]
+    fn unicode_round_trip() {
+        let m: Move = "R\u{2032}".parse().unwrap();
+
+        assert_eq!(m, Move::R_PRIME);
+        assert_eq!(m.to_string_unicode(), "R\u{2032}");
+
+        for m in Move::all_basic() {
+            assert_eq!(m.to_string_unicode().parse(), Ok(m));
+        }
+    }
+}
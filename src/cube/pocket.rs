@@ -0,0 +1,216 @@
+use crate::cube::cube::{Cube, FaceKind, MOVE_PERMUTATIONS};
+use crate::cube::cubie::Color;
+use crate::cube::moves::{Direction, DIRECTIONS, FACE_LAYERS};
+use crate::cube::puzzle::Puzzle;
+use rand::Rng;
+
+/// The index within a face of each of its four stickers on a 3x3x3, which are
+/// the stickers a 2x2x2 shares with it.
+const CORNER_FACELETS: [usize; 4] = [0, 2, 6, 8];
+
+/// The sticker permutations of the 18 face turns of a 2x2x2, in the same
+/// order and form as [`MOVE_PERMUTATIONS`].
+const POCKET_PERMUTATIONS: [[usize; 24]; 18] = pocket_permutations();
+
+/// Returns the index among the 54 facelets of a 3x3x3 of a 2x2x2 sticker.
+const fn facelet_index(i: usize) -> usize {
+    i / 4 * 9 + CORNER_FACELETS[i % 4]
+}
+
+/// Returns the index of the 2x2x2 sticker at a corner facelet of a 3x3x3.
+const fn sticker_index(facelet: usize) -> usize {
+    let corner = match facelet % 9 {
+        0 => 0,
+        2 => 1,
+        6 => 2,
+        _ => 3,
+    };
+
+    facelet / 9 * 4 + corner
+}
+
+const fn pocket_permutations() -> [[usize; 24]; 18] {
+    let mut table = [[0; 24]; 18];
+    let mut m = 0;
+
+    while m < MOVE_PERMUTATIONS.len() {
+        let mut i = 0;
+        while i < 24 {
+            table[m][i] = sticker_index(MOVE_PERMUTATIONS[m][facelet_index(i)]);
+            i += 1;
+        }
+        m += 1;
+    }
+
+    table
+}
+
+/// A turn of one face of a [`PocketCube`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PocketMove {
+    pub face: FaceKind,
+    pub direction: Direction,
+}
+
+impl PocketMove {
+    /// Returns a move which turns a face in a direction.
+    pub const fn new(face: FaceKind, direction: Direction) -> Self {
+        Self {
+            face,
+            direction,
+        }
+    }
+
+    /// Returns the move which undoes this one.
+    pub const fn inverse(&self) -> Self {
+        Self::new(self.face, self.direction.inverse())
+    }
+}
+
+/// A 2x2x2 cube, stored as the colors of its 24 stickers.
+///
+/// Stickers are ordered face by face in the same face order as
+/// [`Cube::facelets`], and each face in reading order, so a pocket cube has
+/// the colors of the corner facelets of a [`Cube`] turned the same way.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, pocket::*};
+/// let mut cube = PocketCube::solved();
+/// let r = PocketMove::new(FaceKind::Right, Direction::Clockwise);
+///
+/// cube.apply(r);
+/// assert!(!cube.is_solved());
+///
+/// cube.apply(r.inverse());
+/// assert!(cube.is_solved());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PocketCube([Color; 24]);
+
+impl PocketCube {
+    /// Returns a solved cube with the standard Western color scheme.
+    pub fn solved() -> Self {
+        let facelets = Cube::solved().facelets();
+        let mut stickers = [Color::Uninit; 24];
+
+        for (i, sticker) in stickers.iter_mut().enumerate() {
+            *sticker = facelets[facelet_index(i)];
+        }
+
+        Self(stickers)
+    }
+
+    /// Returns the colors of the 24 stickers of the cube.
+    pub const fn stickers(&self) -> &[Color; 24] {
+        &self.0
+    }
+
+    /// Applies a [`PocketMove`] to the cube.
+    pub fn apply(&mut self, m: PocketMove) -> &mut Self {
+        let face = FACE_LAYERS.iter().position(|l| *l == m.face.layer());
+        let direction = DIRECTIONS.iter().position(|d| *d == m.direction);
+        let permutation = match (face, direction) {
+            (Some(face), Some(direction)) => {
+                &POCKET_PERMUTATIONS[face * 3 + direction]
+            }
+            _ => panic!("Every face and direction has a permutation"),
+        };
+
+        let old = self.0;
+        for (sticker, from) in self.0.iter_mut().zip(permutation.iter()) {
+            *sticker = old[*from];
+        }
+
+        self
+    }
+
+    /// Returns true if every face is a single color.
+    pub fn is_solved(&self) -> bool {
+        self.0
+            .chunks(4)
+            .all(|face| face.iter().all(|c| *c == face[0]))
+    }
+
+    /// Applies a number of random moves to the cube, never turning the same
+    /// face twice in a row, and returns the moves applied.
+    pub fn scramble(
+        &mut self,
+        len: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<PocketMove> {
+        let faces = FaceKind::all();
+        let mut moves: Vec<PocketMove> = Vec::with_capacity(len);
+
+        while moves.len() < len {
+            let face = faces[rng.gen_range(0, faces.len())];
+            if moves.last().map_or(false, |m| m.face == face) {
+                continue;
+            }
+
+            let direction = DIRECTIONS[rng.gen_range(0, DIRECTIONS.len())];
+            let m = PocketMove::new(face, direction);
+
+            self.apply(m);
+            moves.push(m);
+        }
+
+        moves
+    }
+}
+
+impl Puzzle for PocketCube {
+    type Move = PocketMove;
+
+    fn solved() -> Self {
+        PocketCube::solved()
+    }
+
+    fn apply(&mut self, m: PocketMove) {
+        PocketCube::apply(self, m);
+    }
+
+    fn is_solved(&self) -> bool {
+        PocketCube::is_solved(self)
+    }
+
+    fn scramble(&mut self, len: usize, rng: &mut impl Rng) -> Vec<PocketMove> {
+        PocketCube::scramble(self, len, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::moves::Move;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn matches_cube_corners() {
+        let mut rng = StdRng::seed_from_u64(663);
+        let mut pocket = PocketCube::solved();
+        let mut cube = Cube::solved();
+
+        for m in PocketCube::solved().scramble(100, &mut rng) {
+            pocket.apply(m);
+            cube.apply(Move::new(m.face.layer(), m.direction));
+
+            let facelets = cube.facelets();
+            for (i, sticker) in pocket.stickers().iter().enumerate() {
+                assert_eq!(*sticker, facelets[facelet_index(i)]);
+            }
+        }
+    }
+
+    #[test]
+    fn sexy_move_order() {
+        let r = PocketMove::new(FaceKind::Right, Direction::Clockwise);
+        let u = PocketMove::new(FaceKind::Top, Direction::Clockwise);
+        let mut cube = PocketCube::solved();
+
+        for i in 1..=6 {
+            cube.apply(r).apply(u).apply(r.inverse()).apply(u.inverse());
+            assert_eq!(cube.is_solved(), i == 6);
+        }
+    }
+}
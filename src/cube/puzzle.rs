@@ -0,0 +1,115 @@
+use crate::cube::cube::Cube;
+use crate::cube::facelets::Facelets;
+use crate::cube::moves::Move;
+use crate::cube::scramble::ScrambleIter;
+use rand::Rng;
+
+/// The operations shared by every twisty puzzle, so that tools such as timers
+/// and trainers can be written once and work with any of them.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, facelets::*, pocket::*, puzzle::*};
+/// fn scrambled<P: Puzzle>(len: usize) -> P {
+///     let mut puzzle = P::solved();
+///     puzzle.scramble(len, &mut rand::thread_rng());
+///     puzzle
+/// }
+///
+/// assert!(!scrambled::<Cube>(1).is_solved());
+/// assert!(!scrambled::<Facelets>(1).is_solved());
+/// assert!(!scrambled::<PocketCube>(1).is_solved());
+/// ```
+pub trait Puzzle: Sized {
+    /// The moves which turn the puzzle.
+    type Move;
+
+    /// Returns the puzzle in its solved state.
+    fn solved() -> Self;
+
+    /// Applies a move to the puzzle.
+    fn apply(&mut self, m: Self::Move);
+
+    /// Returns true if the puzzle is solved.
+    fn is_solved(&self) -> bool;
+
+    /// Applies a number of random moves to the puzzle, and returns the moves
+    /// applied.
+    fn scramble(&mut self, len: usize, rng: &mut impl Rng) -> Vec<Self::Move>;
+}
+
+impl Puzzle for Cube {
+    type Move = Move;
+
+    fn solved() -> Self {
+        Cube::solved()
+    }
+
+    fn apply(&mut self, m: Move) {
+        Cube::apply(self, m);
+    }
+
+    fn is_solved(&self) -> bool {
+        Cube::is_solved(self)
+    }
+
+    fn scramble(&mut self, len: usize, rng: &mut impl Rng) -> Vec<Move> {
+        Cube::scramble(self, len, rng)
+    }
+}
+
+impl Puzzle for Facelets {
+    type Move = Move;
+
+    fn solved() -> Self {
+        Facelets::solved()
+    }
+
+    fn apply(&mut self, m: Move) {
+        Facelets::apply(self, m);
+    }
+
+    fn is_solved(&self) -> bool {
+        Facelets::is_solved(self)
+    }
+
+    fn scramble(&mut self, len: usize, rng: &mut impl Rng) -> Vec<Move> {
+        let moves: Vec<Move> = ScrambleIter::new(rng).take(len).collect();
+        self.apply_sequence(&moves);
+
+        moves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::pocket::{PocketCube, PocketMove};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Scrambles a puzzle, then undoes the scramble one move at a time.
+    fn scramble_and_undo<P: Puzzle>(
+        seed: u64,
+        inverse: impl Fn(&P::Move) -> P::Move,
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut puzzle = P::solved();
+        assert!(puzzle.is_solved());
+
+        let scramble = puzzle.scramble(20, &mut rng);
+        assert_eq!(scramble.len(), 20);
+        assert!(!puzzle.is_solved());
+
+        for m in scramble.iter().rev() {
+            puzzle.apply(inverse(m));
+        }
+        assert!(puzzle.is_solved());
+    }
+
+    #[test]
+    fn generic_over_puzzles() {
+        scramble_and_undo::<Cube>(663, Move::inverse);
+        scramble_and_undo::<Facelets>(663, Move::inverse);
+        scramble_and_undo::<PocketCube>(663, PocketMove::inverse);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::cube::moves::{Layer, Move, DIRECTIONS, FACE_LAYERS};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// An endless stream of random face moves which follows the WCA rules for
+/// scrambles: the same face is never turned twice in a row, and after turning
+/// two opposite faces neither of them is turned again straight away, since the
+/// three moves could be merged into two.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{moves::*, scramble::*};
+/// let scramble: Vec<Move> = scramble_iter(646).take(20).collect();
+///
+/// assert_eq!(scramble.len(), 20);
+/// ```
+pub struct ScrambleIter<R: Rng> {
+    rng: R,
+    last: Option<Layer>,
+    before_last: Option<Layer>,
+}
+
+impl<R: Rng> ScrambleIter<R> {
+    /// Creates a stream of scramble moves chosen by a random number generator.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            last: None,
+            before_last: None,
+        }
+    }
+
+    /// Returns true if turning a layer next would make the scramble
+    /// redundant.
+    fn redundant(&self, layer: Layer) -> bool {
+        redundant(layer, self.last, self.before_last)
+    }
+}
+
+/// Returns true if turning a layer after the last two layers turned would make
+/// a scramble redundant: it's the same layer as the last, or the same as the
+/// one before with only its opposite turned in between.
+fn redundant(
+    layer: Layer,
+    last: Option<Layer>,
+    before_last: Option<Layer>,
+) -> bool {
+    match (last, before_last) {
+        (Some(last), _) if last == layer => true,
+        (Some(last), Some(before_last)) => {
+            before_last == layer && last.opposite() == Some(layer)
+        }
+        _ => false,
+    }
+}
+
+impl<R: Rng> Iterator for ScrambleIter<R> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        let layer = loop {
+            let layer = FACE_LAYERS[self.rng.gen_range(0, FACE_LAYERS.len())];
+
+            if !self.redundant(layer) {
+                break layer;
+            }
+        };
+        let direction = DIRECTIONS[self.rng.gen_range(0, DIRECTIONS.len())];
+
+        self.before_last = self.last;
+        self.last = Some(layer);
+
+        Some(Move::new(layer, direction))
+    }
+}
+
+/// Returns an endless stream of scramble moves generated from a seed. The same
+/// seed always gives the same moves.
+pub fn scramble_iter(seed: u64) -> ScrambleIter<StdRng> {
+    ScrambleIter::new(StdRng::seed_from_u64(seed))
+}
+
+/// Relative probabilities of turning each face while scrambling, for
+/// scrambles which favor some faces over others.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{moves::*, scramble::*};
+/// let weights = MoveWeights::uniform().with_weight(Layer::U, 4);
+///
+/// assert_eq!(weights.weight(Layer::U), 4);
+/// assert_eq!(weights.weight(Layer::R), 1);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MoveWeights {
+    weights: [u32; 6],
+}
+
+impl MoveWeights {
+    /// Returns weights which turn every face equally often.
+    pub const fn uniform() -> Self {
+        Self {
+            weights: [1; 6],
+        }
+    }
+
+    /// Returns the weights with a face's weight changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layer isn't one of the six faces.
+    pub fn with_weight(mut self, layer: Layer, weight: u32) -> Self {
+        self.weights[face_index(layer)] = weight;
+
+        self
+    }
+
+    /// Returns the weight of a face.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layer isn't one of the six faces.
+    pub fn weight(&self, layer: Layer) -> u32 {
+        self.weights[face_index(layer)]
+    }
+
+    /// Picks a face at random by weight, skipping any face which would make
+    /// the scramble redundant after the last two faces turned, as in
+    /// [`ScrambleIter`]. Returns None if no other face has any weight.
+    pub(crate) fn pick(
+        &self,
+        last: Option<Layer>,
+        before_last: Option<Layer>,
+        rng: &mut impl Rng,
+    ) -> Option<Layer> {
+        let weight = |layer: &Layer| {
+            if redundant(*layer, last, before_last) {
+                0
+            } else {
+                self.weight(*layer)
+            }
+        };
+
+        let total: u32 = FACE_LAYERS.iter().map(weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0, total);
+        for layer in FACE_LAYERS.iter() {
+            if pick < weight(layer) {
+                return Some(*layer);
+            }
+            pick -= weight(layer);
+        }
+
+        None
+    }
+}
+
+/// Returns the index of a face layer in [`FACE_LAYERS`].
+fn face_index(layer: Layer) -> usize {
+    match FACE_LAYERS.iter().position(|l| *l == layer) {
+        Some(i) => i,
+        None => panic!("Not a face layer"),
+    }
+}
+
+/// Asserts that a scramble never turns the same layer twice in a row, or
+/// turns a layer again with only its opposite turned in between.
+#[cfg(test)]
+pub(crate) fn assert_not_redundant(moves: &[Move]) {
+    for pair in moves.windows(2) {
+        assert_ne!(pair[0].layer, pair[1].layer);
+    }
+
+    for triple in moves.windows(3) {
+        assert!(
+            triple[0].layer != triple[2].layer
+                || triple[1].layer.opposite() != Some(triple[0].layer)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_redundancy_rules() {
+        let moves: Vec<Move> = scramble_iter(646).take(25).collect();
+        assert_eq!(moves.len(), 25);
+
+        assert_not_redundant(&moves);
+    }
+
+    #[test]
+    fn weighted_picks() {
+        let mut rng = StdRng::seed_from_u64(669);
+        let weights = MoveWeights::uniform()
+            .with_weight(Layer::F, 0)
+            .with_weight(Layer::U, 10);
+
+        let mut counts = [0; 6];
+        for _ in 0..1000 {
+            let layer = weights.pick(None, None, &mut rng).unwrap();
+            counts[face_index(layer)] += 1;
+        }
+
+        assert_eq!(counts[face_index(Layer::F)], 0);
+        assert!(counts[face_index(Layer::U)] > counts[face_index(Layer::R)]);
+
+        let only_u = MoveWeights {
+            weights: [1, 0, 0, 0, 0, 0],
+        };
+        assert_eq!(only_u.pick(Some(Layer::U), None, &mut rng), None);
+
+        let only_r_l = MoveWeights {
+            weights: [0, 0, 1, 1, 0, 0],
+        };
+        assert_eq!(
+            only_r_l.pick(Some(Layer::L), Some(Layer::R), &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn seeded() {
+        let a: Vec<Move> = scramble_iter(7).take(25).collect();
+        let b: Vec<Move> = scramble_iter(7).take(25).collect();
+        let c: Vec<Move> = scramble_iter(8).take(25).collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
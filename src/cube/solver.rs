@@ -0,0 +1,270 @@
+use crate::cube::cube::Cube;
+use crate::cube::moves::{Layer, Move, FACE_LAYERS};
+use std::time::Instant;
+
+/// A method of solving a [`Cube`].
+pub trait Solver {
+    /// Returns a sequence of moves which solves the cube, or None if the solver
+    /// can't find one.
+    fn solve(&self, cube: &Cube) -> Option<Vec<Move>>;
+}
+
+/// Finds the shortest solutions to a cube, counted in face turns, using an
+/// iterative deepening depth first search.
+///
+/// The search grows exponentially with the length of the solution, so this is
+/// only practical for cubes a few moves from solved. Moves are always tried in
+/// the same order, so the same cube always gives the same solution.
+///
+/// With the `log` feature enabled, the depth being searched and the number of
+/// nodes searched so far are logged as the search progresses.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, solver::*};
+/// let mut cube = Cube::solved();
+/// cube.apply_sequence(&Move::parse_sequence("R U R'").unwrap());
+///
+/// let solution = OptimalSolver::new(3).solve(&cube).unwrap();
+///
+/// assert_eq!(solution, Move::parse_sequence("R U' R'").unwrap());
+/// ```
+pub struct OptimalSolver {
+    pub max_depth: usize,
+}
+
+impl OptimalSolver {
+    pub const fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+        }
+    }
+
+    /// Searches like [`Solver::solve`], but gives up and returns None once the
+    /// deadline has passed. The clock is only checked every
+    /// [`CLOCK_INTERVAL`] nodes, so the search may run slightly past it.
+    pub fn solve_until(
+        &self,
+        cube: &Cube,
+        deadline: Instant,
+    ) -> Option<Vec<Move>> {
+        self.run(cube, Some(deadline))
+    }
+
+    fn run(&self, cube: &Cube, deadline: Option<Instant>) -> Option<Vec<Move>> {
+        let mut cube = cube.clone();
+        let mut path = Vec::new();
+        let mut nodes = 0;
+
+        for depth in 0..=self.max_depth {
+            #[cfg(feature = "log")]
+            log::debug!("Searching depth {} after {} nodes", depth, nodes);
+
+            match search(
+                &mut cube, depth, None, &mut path, &mut nodes, deadline,
+            ) {
+                Some(true) => {
+                    #[cfg(feature = "log")]
+                    log::debug!("Found a solution after {} nodes", nodes);
+
+                    return Some(path);
+                }
+                Some(false) => {}
+                None => {
+                    #[cfg(feature = "log")]
+                    log::debug!("Ran out of time after {} nodes", nodes);
+
+                    return None;
+                }
+            }
+        }
+
+        #[cfg(feature = "log")]
+        log::debug!("No solution within {} moves", self.max_depth);
+
+        None
+    }
+}
+
+impl Solver for OptimalSolver {
+    fn solve(&self, cube: &Cube) -> Option<Vec<Move>> {
+        self.run(cube, None)
+    }
+}
+
+/// The number of nodes searched between each check of a search's deadline.
+pub const CLOCK_INTERVAL: u64 = 1024;
+
+/// Searches every sequence of face moves of a given length for one which
+/// solves the cube, leaving it in `path` if found. `nodes` counts every state
+/// visited. Returns None if the deadline passes before the search finishes.
+fn search(
+    cube: &mut Cube,
+    depth: usize,
+    last: Option<Move>,
+    path: &mut Vec<Move>,
+    nodes: &mut u64,
+    deadline: Option<Instant>,
+) -> Option<bool> {
+    *nodes += 1;
+
+    if *nodes % CLOCK_INTERVAL == 0
+        && deadline.map_or(false, |deadline| Instant::now() >= deadline)
+    {
+        return None;
+    }
+
+    if depth == 0 {
+        return Some(cube.is_solved());
+    }
+
+    for m in legal_moves_after(last) {
+        cube.apply(m);
+        path.push(m);
+
+        if search(cube, depth - 1, Some(m), path, nodes, deadline)? {
+            return Some(true);
+        }
+
+        path.pop();
+        cube.apply(m.inverse());
+    }
+
+    Some(false)
+}
+
+/// Returns the face moves worth searching after a move, in the order of
+/// [`Move::all_basic`]. Moves which turn the same face again are left out, as
+/// are moves which turn the opposite face in the order that isn't searched.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{moves::*, solver::*};
+/// assert_eq!(legal_moves_after(None).len(), 18);
+/// assert_eq!(legal_moves_after(Some(Move::U)).len(), 15);
+/// assert_eq!(legal_moves_after(Some(Move::D)).len(), 12);
+/// ```
+pub fn legal_moves_after(prev: Option<Move>) -> Vec<Move> {
+    Move::all_basic()
+        .filter(|m| prev.map_or(true, |prev| !redundant(prev.layer, m.layer)))
+        .collect()
+}
+
+/// Returns true if turning a layer straight after another can be skipped while
+/// searching. Turning the same layer twice can always be merged into one move,
+/// and since opposite layers commute only one order of them needs searching.
+pub(crate) fn redundant(last: Layer, next: Layer) -> bool {
+    let order = |layer| FACE_LAYERS.iter().position(|l| *l == layer);
+
+    last == next || (last.opposite() == Some(next) && order(next) < order(last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::cube::FaceKind;
+
+    #[test]
+    fn solves_short_scrambles() {
+        let solver = OptimalSolver::new(4);
+
+        for scramble in &["", "F", "R U", "L' D2 B", "R U R' U'", "R L R'"] {
+            let mut cube = Cube::solved();
+            cube.apply_sequence(&Move::parse_sequence(scramble).unwrap());
+
+            let solution = solver.solve(&cube).unwrap();
+            cube.apply_sequence(&solution);
+
+            assert!(cube.is_solved());
+        }
+    }
+
+    #[test]
+    fn solutions_are_optimal() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R L R'").unwrap());
+
+        assert_eq!(
+            OptimalSolver::new(3).solve(&cube),
+            Some(vec![Move::L_PRIME])
+        );
+    }
+
+    #[test]
+    fn solutions_are_deterministic() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R2 U' F B'").unwrap());
+
+        let solver = OptimalSolver::new(4);
+        let first = solver.solve(&cube).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(solver.solve(&cube).unwrap(), first);
+        }
+
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("D L2 F' U R' B").unwrap());
+
+        let first = cube.solve_cross(FaceKind::Bottom);
+        assert_eq!(cube.solve_cross(FaceKind::Bottom), first);
+    }
+
+    #[test]
+    fn legal_moves() {
+        let after_r = legal_moves_after(Some(Move::R));
+        assert_eq!(after_r.len(), 12);
+        assert!(after_r.iter().all(|m| m.layer != Layer::R));
+        assert!(after_r.iter().all(|m| m.layer != Layer::L));
+
+        let after_l = legal_moves_after(Some(Move::L2));
+        assert_eq!(after_l.len(), 15);
+        assert!(after_l.iter().all(|m| m.layer != Layer::L));
+        assert!(after_l.contains(&Move::R_PRIME));
+    }
+
+    #[test]
+    fn gives_up_past_max_depth() {
+        let mut cube = Cube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+
+        assert_eq!(OptimalSolver::new(2).solve(&cube), None);
+    }
+
+    #[cfg(feature = "log")]
+    mod logging {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RECORDS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingLogger;
+
+        impl log::Log for CountingLogger {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, _: &log::Record) {
+                RECORDS.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CountingLogger = CountingLogger;
+
+        #[test]
+        fn solving_logs_progress() {
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(log::LevelFilter::Trace);
+
+            let mut cube = Cube::solved();
+            cube.apply_sequence(&Move::parse_sequence("R U").unwrap());
+
+            let before = RECORDS.load(Ordering::SeqCst);
+            OptimalSolver::new(2).solve(&cube).unwrap();
+
+            assert!(RECORDS.load(Ordering::SeqCst) > before);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::cube::cube::{layer_contains, rotate_position, Cube, FaceKind};
+use crate::cube::moves::Move;
+
+/// A vector in the cube's coordinates, where x runs left to right, y runs
+/// bottom to top, and z runs back to front.
+type Vector = (i8, i8, i8);
+
+/// A [`Cube`] which also tracks which way each of its centers is turned, as
+/// on a supercube or a picture cube, where turning a face four times is the
+/// only way a single turn of its center can be undone.
+///
+/// Each center carries a marker pointing along its face, which starts
+/// pointing to the back on the top and bottom centers and to the top on every
+/// other center, and turns along with the center.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, supercube::*};
+/// let mut cube = SuperCube::solved();
+/// let moves = Move::parse_sequence("U R L U2 R' L' U R L U2 R' L'").unwrap();
+///
+/// cube.apply_sequence(&moves);
+///
+/// assert!(cube.cube().is_solved());
+/// assert!(!cube.is_solved());
+/// assert_eq!(cube.center_rotation(FaceKind::Top), 2);
+/// ```
+#[derive(Clone)]
+pub struct SuperCube {
+    cube: Cube,
+    /// The position and marker of each center, in the order of the faces
+    /// they belong on in [`FaceKind::all`].
+    centers: [(Vector, Vector); 6],
+}
+
+impl SuperCube {
+    /// Returns a solved supercube with the standard Western color scheme and
+    /// every center turned to its starting rotation.
+    pub fn solved() -> Self {
+        let mut centers = [((0, 0, 0), (0, 0, 0)); 6];
+
+        for (center, face) in centers.iter_mut().zip(FaceKind::all().iter()) {
+            *center = (face.normal(), marker(face.normal()));
+        }
+
+        Self {
+            cube: Cube::solved(),
+            centers,
+        }
+    }
+
+    /// Returns the colors of the cube, without its center rotations.
+    pub const fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Applies a [`Move`] to the cube, turning the centers of the layer along
+    /// with the rest of its stickers.
+    pub fn apply(&mut self, m: Move) -> &mut Self {
+        self.cube.apply(m);
+
+        for _ in 0..m.direction.quarter_turns() {
+            for (pos, marker) in self.centers.iter_mut() {
+                if layer_contains(m.layer, *pos) {
+                    *pos = rotate_position(m.layer, *pos);
+                    *marker = rotate_position(m.layer, *marker);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Applies each [`Move`] of a sequence to the cube in order.
+    pub fn apply_sequence(&mut self, moves: &[Move]) -> &mut Self {
+        for m in moves {
+            self.apply(*m);
+        }
+
+        self
+    }
+
+    /// Returns how many clockwise quarter turns the center on a face has been
+    /// turned from its starting rotation, from 0 to 3.
+    pub fn center_rotation(&self, face: FaceKind) -> u8 {
+        let (_, current) = self
+            .centers
+            .iter()
+            .find(|(pos, _)| *pos == face.normal())
+            .expect("Every face has a center");
+
+        let mut turned = marker(face.normal());
+        let mut turns = 0;
+
+        while turned != *current {
+            turned = rotate_position(face.layer(), turned);
+            turns += 1;
+        }
+
+        turns
+    }
+
+    /// Returns true if every face is a single color and every center is
+    /// turned the right way. Since rotating the whole cube doesn't unsolve
+    /// it, the centers only need to be turned the same way as each other.
+    pub fn is_solved(&self) -> bool {
+        if !self.cube.is_solved() {
+            return false;
+        }
+
+        // The whole cube rotation which took the top and front centers to
+        // where they are now, as the images of the x, y and z axes.
+        let y = self.centers[0].0;
+        let z = self.centers[3].0;
+        let x = cross(y, z);
+
+        FaceKind::all().iter().zip(self.centers.iter()).all(
+            |(face, (_, current))| {
+                let (a, b, c) = marker(face.normal());
+
+                (0..3).all(|i| {
+                    let axis = |v: Vector| [v.0, v.1, v.2][i];
+
+                    a * axis(x) + b * axis(y) + c * axis(z) == axis(*current)
+                })
+            },
+        )
+    }
+}
+
+/// Returns the starting marker of the center facing along a normal.
+const fn marker(normal: Vector) -> Vector {
+    match normal {
+        (0, _, 0) => (0, 0, -1),
+        _ => (0, 1, 0),
+    }
+}
+
+/// Returns the cross product of two vectors.
+const fn cross(a: Vector, b: Vector) -> Vector {
+    (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_turns_rotate_centers() {
+        let mut plain = Cube::solved();
+        let mut cube = SuperCube::solved();
+
+        for turns in 1..=4 {
+            plain.apply(Move::U);
+            cube.apply(Move::U);
+
+            assert_eq!(cube.center_rotation(FaceKind::Top), turns % 4);
+            assert_eq!(cube.is_solved(), turns == 4);
+        }
+        assert!(plain.is_solved());
+
+        cube.apply(Move::R_PRIME);
+        assert_eq!(cube.center_rotation(FaceKind::Right), 3);
+    }
+
+    #[test]
+    fn center_only_changes() {
+        let mut cube = SuperCube::solved();
+        let moves = Move::parse_sequence("U R L U2 R' L'").unwrap();
+
+        cube.apply_sequence(&moves).apply_sequence(&moves);
+        assert!(cube.cube().is_solved());
+        assert!(!cube.is_solved());
+        assert_eq!(cube.center_rotation(FaceKind::Top), 2);
+    }
+
+    #[test]
+    fn rotations_stay_solved() {
+        for rotations in &["x", "y'", "z2", "x y", "R L' M'"] {
+            let mut cube = SuperCube::solved();
+            cube.apply_sequence(&Move::parse_sequence(rotations).unwrap());
+
+            assert!(cube.is_solved());
+        }
+
+        let mut cube = SuperCube::solved();
+        cube.apply_sequence(&Move::parse_sequence("y U'").unwrap());
+        assert!(!cube.is_solved());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::cube::cube::{
+    element_faces, element_index, element_position, Cube, FaceKind,
+};
+use crate::cube::cubie::Color;
+use crate::cube::moves::Move;
+
+/// The 24 whole cube rotations, each given as the rotation moves which perform
+/// it. The first move picks the face turned to the top, and the second turns
+/// the cube about the vertical axis.
+const ROTATIONS: [&[Move]; 24] = [
+    &[],
+    &[Move::Y],
+    &[Move::Y2],
+    &[Move::Y_PRIME],
+    &[Move::X],
+    &[Move::X, Move::Y],
+    &[Move::X, Move::Y2],
+    &[Move::X, Move::Y_PRIME],
+    &[Move::X2],
+    &[Move::X2, Move::Y],
+    &[Move::X2, Move::Y2],
+    &[Move::X2, Move::Y_PRIME],
+    &[Move::X_PRIME],
+    &[Move::X_PRIME, Move::Y],
+    &[Move::X_PRIME, Move::Y2],
+    &[Move::X_PRIME, Move::Y_PRIME],
+    &[Move::Z],
+    &[Move::Z, Move::Y],
+    &[Move::Z, Move::Y2],
+    &[Move::Z, Move::Y_PRIME],
+    &[Move::Z_PRIME],
+    &[Move::Z_PRIME, Move::Y],
+    &[Move::Z_PRIME, Move::Y2],
+    &[Move::Z_PRIME, Move::Y_PRIME],
+];
+
+/// The number of symmetries of the cube: 24 rotations, each with or without a
+/// left-right mirror.
+pub const SYMMETRY_COUNT: u8 = 48;
+
+/// Returns the state of a cube conjugated by one of its 48 symmetries.
+///
+/// Symmetries 0 to 23 are whole cube rotations, and 24 to 47 are the same
+/// rotations applied after mirroring the cube left to right. After the cube is
+/// transformed its stickers are recolored so that its centers keep their
+/// original colors, so symmetric states such as the ones after `R` and `U`
+/// map onto each other.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, symmetry::*};
+/// let mut r = Cube::solved();
+/// r.apply(Move::R);
+///
+/// let mut l = Cube::solved();
+/// l.apply(Move::L_PRIME);
+///
+/// assert_eq!(symmetry(&r, 24).facelets(), l.facelets());
+/// ```
+pub fn symmetry(cube: &Cube, index: u8) -> Cube {
+    let mut result = if index >= 24 {
+        mirrored(cube)
+    } else {
+        cube.clone()
+    };
+    result.apply_sequence(ROTATIONS[(index % 24) as usize]);
+
+    let from: Vec<Color> = FaceKind::all()
+        .iter()
+        .map(|f| result.face_colors(*f)[4])
+        .collect();
+    let to: Vec<Color> = FaceKind::all()
+        .iter()
+        .map(|f| cube.face_colors(*f)[4])
+        .collect();
+
+    for element in result.elements.iter_mut() {
+        for face in element.face_slice_mut() {
+            if let Some(i) = from.iter().position(|c| *c == face.color) {
+                face.color = to[i];
+            }
+        }
+    }
+
+    result
+}
+
+/// Reduces a cube state by its 48 symmetries, returning the canonical
+/// representative of the states symmetric to it along with the index of the
+/// [`symmetry`] which maps the cube onto the representative.
+///
+/// The representative is the symmetric state whose facelets are smallest when
+/// compared color by color, so every state in the same symmetry class reduces
+/// to the same representative. Storing only representatives shrinks tables
+/// of cube states by up to 48 times.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, symmetry::*};
+/// let mut r = Cube::solved();
+/// r.apply(Move::R);
+///
+/// let mut u = Cube::solved();
+/// u.apply(Move::U);
+///
+/// assert_eq!(
+///     symmetry_reduce(&r).0.facelets(),
+///     symmetry_reduce(&u).0.facelets()
+/// );
+/// ```
+pub fn symmetry_reduce(cube: &Cube) -> (Cube, u8) {
+    let mut best = (cube.clone(), 0);
+    let mut best_key = key(cube);
+
+    for index in 1..SYMMETRY_COUNT {
+        let candidate = symmetry(cube, index);
+        let candidate_key = key(&candidate);
+
+        if candidate_key < best_key {
+            best = (candidate, index);
+            best_key = candidate_key;
+        }
+    }
+
+    best
+}
+
+/// Returns the key which representatives are chosen by.
+fn key(cube: &Cube) -> Vec<u8> {
+    cube.facelets().iter().map(|c| *c as u8).collect()
+}
+
+/// Returns a copy of a cube reflected left to right, without recoloring.
+fn mirrored(cube: &Cube) -> Cube {
+    let mut result = cube.clone();
+
+    for (i, element) in cube.elements.iter().enumerate() {
+        let (x, y, z) = element_position(i);
+        let j = element_index((-x, y, z));
+        let to = element_faces(j);
+
+        for (face, kind) in element.face_slice().iter().zip(&element_faces(i)) {
+            let (x, y, z) = kind.normal();
+            let reflected = FaceKind::from_normal((-x, y, z));
+
+            if let Some(k) = to.iter().position(|f| *f == reflected) {
+                result.elements[j].face_slice_mut()[k].color = face.color;
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn after(moves: &str) -> Cube {
+        let mut c = Cube::solved();
+        c.apply_sequence(&Move::parse_sequence(moves).unwrap());
+        c
+    }
+
+    #[test]
+    fn symmetric_states_reduce_together() {
+        let expected = symmetry_reduce(&after("R")).0.facelets();
+
+        for m in &["U", "D", "L", "R", "F", "B", "U'", "D'", "L'", "R'", "F'"] {
+            assert_eq!(symmetry_reduce(&after(m)).0.facelets(), expected);
+        }
+
+        let expected = symmetry_reduce(&after("R U")).0.facelets();
+        assert_eq!(symmetry_reduce(&after("F R")).0.facelets(), expected);
+        assert_eq!(symmetry_reduce(&after("L' U'")).0.facelets(), expected);
+        assert_ne!(symmetry_reduce(&after("R U'")).0.facelets(), expected);
+    }
+
+    #[test]
+    fn reduction_index_maps_to_representative() {
+        let cube = after("R U2 F' L D' B");
+        let (representative, index) = symmetry_reduce(&cube);
+
+        assert_eq!(
+            symmetry(&cube, index).facelets(),
+            representative.facelets()
+        );
+
+        for index in 0..SYMMETRY_COUNT {
+            let symmetric = symmetry(&cube, index);
+
+            assert_eq!(
+                symmetry_reduce(&symmetric).0.facelets(),
+                representative.facelets()
+            );
+        }
+    }
+
+    #[test]
+    fn solved_is_symmetric() {
+        let solved = Cube::solved();
+
+        for index in 0..SYMMETRY_COUNT {
+            assert!(symmetry(&solved, index).is_solved());
+        }
+    }
+}
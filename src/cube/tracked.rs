@@ -0,0 +1,152 @@
+use crate::cube::cube::Cube;
+use crate::cube::moves::{Move, FACE_LAYERS};
+
+/// A [`Cube`] which records every [`Move`] applied to it, so that moves can be
+/// undone or the history replayed later.
+///
+/// Moves can also be applied in batches with [`Extend`], recording each of
+/// them in order.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, tracked::*};
+/// let mut cube = TrackedCube::solved();
+/// cube.extend(Move::parse_sequence("R U R'").unwrap());
+///
+/// assert_eq!(cube.history(), &[Move::R, Move::U, Move::R_PRIME]);
+///
+/// while cube.undo().is_some() {}
+/// assert!(cube.cube().is_solved());
+/// ```
+#[derive(Clone)]
+pub struct TrackedCube {
+    cube: Cube,
+    history: Vec<Move>,
+    counts: [usize; 6],
+}
+
+impl TrackedCube {
+    /// Returns a solved cube with the standard Western color scheme and no
+    /// history.
+    pub fn solved() -> Self {
+        Self::from(Cube::solved())
+    }
+
+    /// Returns the underlying cube.
+    pub const fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Returns every move applied to the cube, oldest first.
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// Returns how many times each outer face has been turned, in the order
+    /// U, D, L, R, F, B. Double turns count once, and slice moves, wide moves
+    /// and rotations aren't counted. Undoing a move doesn't take its turn off
+    /// the counts, since the face was still turned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustytwisty::cube::{moves::*, tracked::*};
+    /// let mut cube = TrackedCube::solved();
+    /// cube.apply_sequence(&Move::parse_sequence("R U R' U2 M").unwrap());
+    /// cube.undo();
+    ///
+    /// assert_eq!(cube.turn_counts(), [2, 0, 0, 2, 0, 0]);
+    /// ```
+    pub const fn turn_counts(&self) -> [usize; 6] {
+        self.counts
+    }
+
+    /// Applies a [`Move`] to the cube and records it.
+    pub fn apply(&mut self, m: Move) -> &mut Self {
+        self.cube.apply(m);
+        self.history.push(m);
+
+        if let Some(i) = FACE_LAYERS.iter().position(|l| *l == m.layer) {
+            self.counts[i] += 1;
+        }
+
+        self
+    }
+
+    /// Applies and records each [`Move`] of a sequence in order.
+    pub fn apply_sequence(&mut self, moves: &[Move]) -> &mut Self {
+        self.extend(moves.iter().copied());
+
+        self
+    }
+
+    /// Undoes the last recorded move, removing it from the history and
+    /// returning it. Returns None if there are no moves to undo.
+    pub fn undo(&mut self) -> Option<Move> {
+        let m = self.history.pop()?;
+        self.cube.apply(m.inverse());
+
+        Some(m)
+    }
+}
+
+impl From<Cube> for TrackedCube {
+    fn from(cube: Cube) -> Self {
+        Self {
+            cube,
+            history: Vec::new(),
+            counts: [0; 6],
+        }
+    }
+}
+
+impl Extend<Move> for TrackedCube {
+    fn extend<I: IntoIterator<Item = Move>>(&mut self, moves: I) {
+        for m in moves {
+            self.apply(m);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_records_moves() {
+        let mut cube = TrackedCube::solved();
+        cube.apply(Move::F);
+
+        let moves = Move::parse_sequence("R U2 L'").unwrap();
+        cube.extend(moves.iter().copied());
+
+        assert_eq!(cube.history().len(), 4);
+        assert_eq!(&cube.history()[1..], &moves[..]);
+
+        let mut expected = Cube::solved();
+        expected.apply(Move::F).apply_sequence(&moves);
+        assert_eq!(cube.cube().facelets(), expected.facelets());
+
+        assert_eq!(cube.undo(), Some(Move::L_PRIME));
+        assert_eq!(cube.history().len(), 3);
+    }
+
+    #[test]
+    fn counts_face_turns() {
+        let mut cube = TrackedCube::solved();
+        cube.apply(Move::R).apply(Move::R).apply(Move::U);
+        cube.extend(vec![Move::D2, Move::M]);
+
+        assert_eq!(cube.turn_counts(), [1, 1, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn undo_keeps_turn_counts() {
+        let mut cube = TrackedCube::solved();
+        cube.apply_sequence(&Move::parse_sequence("R U F").unwrap());
+
+        while cube.undo().is_some() {}
+
+        assert!(cube.history().is_empty());
+        assert_eq!(cube.turn_counts(), [1, 0, 0, 1, 1, 0]);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::cube::cube::{element_position, layer_contains, Cube};
+use crate::cube::moves::{Layer, Move};
+
+/// Hashes [`Cube`] states using Zobrist hashing, for use in transposition
+/// tables while searching.
+///
+/// Every (sticker, color) pair is assigned a random 64-bit key, and a cube's
+/// hash is every key for its current stickers XORed together. Because a move
+/// only changes the stickers of the layer it turns, the hash can be updated
+/// after a move without rehashing the whole cube.
+///
+/// # Examples
+/// ```
+/// # use rustytwisty::cube::{cube::*, moves::*, zobrist::*};
+/// let hasher = ZobristHasher::new(42);
+/// let mut cube = Cube::solved();
+///
+/// let hash = hasher.hash(&cube);
+/// let hash = hasher.apply(hash, &mut cube, Move::R);
+///
+/// assert_eq!(hash, hasher.hash(&cube));
+/// ```
+pub struct ZobristHasher {
+    keys: [[u64; 7]; 78],
+}
+
+impl ZobristHasher {
+    /// Creates a hasher with keys generated from a seed. Hashes are only
+    /// comparable between hashers created with the same seed.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut keys = [[0; 7]; 78];
+
+        for sticker in keys.iter_mut() {
+            for key in sticker.iter_mut() {
+                *key = splitmix64(&mut state);
+            }
+        }
+
+        Self {
+            keys,
+        }
+    }
+
+    /// Computes the hash of a cube from all of its stickers.
+    pub fn hash(&self, cube: &Cube) -> u64 {
+        (0..cube.elements.len()).fold(0, |hash, i| hash ^ self.element(cube, i))
+    }
+
+    /// Applies a move to a cube, returning the cube's new hash computed
+    /// incrementally from its hash before the move.
+    pub fn apply(&self, hash: u64, cube: &mut Cube, m: Move) -> u64 {
+        let hash = hash ^ self.layer(cube, m.layer);
+        cube.apply(m);
+
+        hash ^ self.layer(cube, m.layer)
+    }
+
+    /// Returns the hash of just the stickers within a layer of the cube.
+    fn layer(&self, cube: &Cube, layer: Layer) -> u64 {
+        (0..cube.elements.len())
+            .filter(|i| layer_contains(layer, element_position(*i)))
+            .fold(0, |hash, i| hash ^ self.element(cube, i))
+    }
+
+    /// Returns the hash of just the stickers of a single element of the cube.
+    fn element(&self, cube: &Cube, index: usize) -> u64 {
+        cube.elements[index].face_slice().iter().enumerate().fold(
+            0,
+            |hash, (k, face)| {
+                hash ^ self.keys[index * 3 + k][face.color as usize]
+            },
+        )
+    }
+}
+
+/// Advances a SplitMix64 generator, returning its next output.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incremental_matches_full() {
+        let hasher = ZobristHasher::new(7);
+        let mut cube = Cube::solved();
+        let mut hash = hasher.hash(&cube);
+
+        let moves = Move::parse_sequence("R U2 F' M x D' E2 B L' S y z2");
+        for m in moves.unwrap() {
+            hash = hasher.apply(hash, &mut cube, m);
+
+            assert_eq!(hash, hasher.hash(&cube));
+        }
+    }
+
+    #[test]
+    fn different_states_differ() {
+        let hasher = ZobristHasher::new(7);
+        let solved = hasher.hash(&Cube::solved());
+
+        let mut cube = Cube::solved();
+        cube.apply(Move::R);
+        assert_ne!(hasher.hash(&cube), solved);
+
+        cube.apply(Move::R_PRIME);
+        assert_eq!(hasher.hash(&cube), solved);
+    }
+}
@@ -1,5 +1,6 @@
 #![feature(const_fn)]
 #![feature(const_if_match)]
+#![feature(const_loop)]
 #![feature(const_panic)]
 #![feature(const_trait_impl)]
 